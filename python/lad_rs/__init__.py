@@ -0,0 +1,19 @@
+from ._lad_rs import (
+    Binarizer,
+    CancellationToken,
+    FitReport,
+    LadError,
+    Pattern,
+    RuleGenerator,
+    SupportSetSelector,
+)
+
+__all__ = [
+    "Binarizer",
+    "CancellationToken",
+    "FitReport",
+    "LadError",
+    "Pattern",
+    "RuleGenerator",
+    "SupportSetSelector",
+]
@@ -0,0 +1,260 @@
+"""scikit-learn compatible estimators built on top of ``Binarizer`` and ``RuleGenerator``."""
+
+import numpy as np
+import polars as pl
+from sklearn.base import BaseEstimator, ClassifierMixin, TransformerMixin
+from sklearn.utils.validation import check_is_fitted
+
+from ._lad_rs import Binarizer, RuleGenerator
+
+__all__ = ["LADBinarizer", "LADClassifier"]
+
+
+def _to_frame(X):
+    if isinstance(X, pl.DataFrame):
+        return X
+    if isinstance(X, np.ndarray):
+        return pl.DataFrame(X, schema=[f"x{i}" for i in range(X.shape[1])])
+    return pl.DataFrame(X)
+
+
+def _to_series(y):
+    if isinstance(y, pl.Series):
+        return y
+    if isinstance(y, np.ndarray):
+        return pl.Series("y", y)
+    return pl.Series(y)
+
+
+def _weights(sample_weight):
+    if sample_weight is None:
+        return None
+    return pl.Series("weight", np.asarray(sample_weight, dtype=np.float64))
+
+
+def _binarizer(estimator):
+    kwargs = {
+        "threshold": estimator.threshold,
+        "nominal_size": estimator.nominal_size,
+        "intervals": estimator.intervals,
+        "null_policy": estimator.null_policy,
+        "criterion": estimator.criterion,
+    }
+    if estimator.max_cutpoints_per_column is not None:
+        kwargs["max_cutpoints_per_column"] = estimator.max_cutpoints_per_column
+    return Binarizer(**kwargs)
+
+
+class _PickledModel:
+    """Pickles the fitted model in ``_model`` through its binary format, as the types of the
+    extension cannot be pickled themselves."""
+
+    _model = None
+    _model_type = None
+
+    def __getstate__(self):
+        state = dict(super().__getstate__())
+        if self._model in state:
+            state[self._model] = state[self._model].to_bytes()
+        return state
+
+    def __setstate__(self, state):
+        if self._model in state:
+            state[self._model] = self._model_type.from_bytes(state[self._model])
+        super().__setstate__(state)
+
+
+class LADBinarizer(_PickledModel, TransformerMixin, BaseEstimator):
+    """Turns numeric and nominal columns into binary features.
+
+    ``null_policy`` is one of ``"indicator"``, ``"unknown"`` or ``"drop"``. Dropping rows breaks
+    the one output row per input row contract of scikit-learn, so prefer the other two in
+    pipelines. ``criterion`` scores cutpoints against ``threshold``: ``"score"``,
+    ``"information_gain"``, ``"gain_ratio"``, ``"gini"``, ``"chi_square"`` or ``"mdl"``.
+    """
+
+    _model = "binarizer_"
+    _model_type = Binarizer
+
+    def __init__(
+        self,
+        threshold=0.0,
+        nominal_size=2,
+        max_cutpoints_per_column=None,
+        intervals=False,
+        null_policy="indicator",
+        criterion="score",
+    ):
+        self.threshold = threshold
+        self.nominal_size = nominal_size
+        self.max_cutpoints_per_column = max_cutpoints_per_column
+        self.intervals = intervals
+        self.null_policy = null_policy
+        self.criterion = criterion
+
+    def fit(self, X, y, sample_weight=None):
+        X = _to_frame(X)
+        self.binarizer_ = _binarizer(self)
+        self.binarizer_.fit(X, _to_series(y), _weights(sample_weight))
+        self.feature_names_in_ = np.array(X.columns, dtype=object)
+        self.n_features_in_ = X.width
+        self._feature_names_out = self.binarizer_.transform(X).columns
+        return self
+
+    def transform(self, X):
+        check_is_fitted(self, "binarizer_")
+        return self.binarizer_.transform(_to_frame(X))
+
+    def get_feature_names_out(self, input_features=None):
+        check_is_fitted(self, "binarizer_")
+        return np.array(self._feature_names_out, dtype=object)
+
+
+class LADClassifier(_PickledModel, ClassifierMixin, BaseEstimator):
+    """Logical Analysis of Data classifier.
+
+    ``max_degree`` bounds the number of literals per pattern, 0 means no bound.
+    ``decision_mode`` is one of ``"first_match"``, ``"majority_vote"`` or
+    ``"weighted_discriminant"``. ``min_purity`` below 1 accepts patterns covering a few rows
+    of other classes. ``min_coverage`` is the fewest rows of its class a pattern must cover,
+    as a count when an int and as a share of the class when a float. ``prune``, ``"greedy"``
+    or ``"exact"``, drops patterns not needed to cover the training rows after fitting.
+    ``strategy`` is ``"bottom_up"``, ``"top_down"`` or ``"max_coverage"``, ``"top_down"``
+    ignoring ``max_degree``. ``pattern_type`` is ``"prime"``, ``"spanned"`` or ``"strong"``.
+    ``sample_weight`` given to ``fit`` weighs rows in cutpoint scores, pattern purity and
+    statistics and the fallback class, ``min_coverage`` still counting rows.
+
+    For imbalanced classes, ``class_min_coverage`` maps classes to their own ``min_coverage``
+    and ``max_patterns`` maps classes to the most patterns they may get. Rows no pattern
+    covers get the class of ``fallback``: ``"most_remaining"`` for the class with the most such
+    training rows, ``"priors"`` for the most frequent class with ``predict_proba`` returning
+    the class frequencies, or ``fallback_label`` when given.
+
+    ``max_duration``, in seconds, and ``max_candidates_per_degree`` stop pattern search early,
+    keeping the patterns found so far. ``fit_report_`` says why the search stopped.
+    """
+
+    _model = "rules_"
+    _model_type = RuleGenerator
+
+    def __init__(
+        self,
+        threshold=0.0,
+        nominal_size=2,
+        max_cutpoints_per_column=None,
+        intervals=False,
+        null_policy="indicator",
+        criterion="score",
+        max_degree=0,
+        decision_mode="first_match",
+        min_purity=1.0,
+        min_coverage=1,
+        prune=None,
+        strategy="bottom_up",
+        pattern_type="prime",
+        fallback="most_remaining",
+        fallback_label=None,
+        class_min_coverage=None,
+        max_patterns=None,
+        max_duration=None,
+        max_candidates_per_degree=None,
+    ):
+        self.threshold = threshold
+        self.nominal_size = nominal_size
+        self.max_cutpoints_per_column = max_cutpoints_per_column
+        self.intervals = intervals
+        self.null_policy = null_policy
+        self.criterion = criterion
+        self.max_degree = max_degree
+        self.decision_mode = decision_mode
+        self.min_purity = min_purity
+        self.min_coverage = min_coverage
+        self.prune = prune
+        self.strategy = strategy
+        self.pattern_type = pattern_type
+        self.fallback = fallback
+        self.fallback_label = fallback_label
+        self.class_min_coverage = class_min_coverage
+        self.max_patterns = max_patterns
+        self.max_duration = max_duration
+        self.max_candidates_per_degree = max_candidates_per_degree
+
+    def fit(self, X, y, sample_weight=None):
+        X = _to_frame(X)
+        y = _to_series(y)
+        weights = _weights(sample_weight)
+        binarizer = _binarizer(self)
+        binarizer.fit(X, y, weights)
+        self.rules_ = RuleGenerator(
+            binarizer,
+            self.max_degree,
+            self.decision_mode,
+            self.min_purity,
+            self.min_coverage,
+            self.strategy,
+            self.pattern_type,
+            fallback=self.fallback,
+            fallback_label=self.fallback_label,
+            label_min_coverage=self.class_min_coverage,
+            max_patterns=self.max_patterns,
+            max_duration=self.max_duration,
+            max_candidates_per_degree=self.max_candidates_per_degree,
+        )
+        binary = binarizer.transform(X)
+        kept = binarizer.kept_rows(X)
+        y_kept = y.filter(kept)
+        self.fit_report_ = self.rules_.fit(
+            binary, y_kept, None if weights is None else weights.filter(kept)
+        )
+        if self.prune is not None:
+            self.rules_.prune(binary, y_kept, self.prune)
+
+        # Patterns, explanations and scores name classes by their text, map it to the values.
+        originals = self.rules_.get_classes().to_list()
+        self._label_map = dict(zip(self.rules_.get_labels(), originals))
+        self.classes_ = np.array(sorted(originals))
+        self.feature_names_in_ = np.array(X.columns, dtype=object)
+        self.n_features_in_ = X.width
+        return self
+
+    def predict(self, X):
+        check_is_fitted(self, "rules_")
+        return self.rules_.predict(_to_frame(X)).to_numpy()
+
+    def predict_proba(self, X):
+        """Share of the score of each class among the patterns covering a row, in the order of
+        ``classes_``."""
+        check_is_fitted(self, "rules_")
+        proba = self.rules_.predict_proba(_to_frame(X))
+        columns = {self._label_map[c]: c for c in proba.columns}
+        return np.column_stack([proba[columns[c]].to_numpy() for c in self.classes_])
+
+    def explain(self, X):
+        """For each row, the predicted label, whether it came from the fallback label, and the
+        ``(label, literals)`` of the patterns covering the row."""
+        check_is_fitted(self, "rules_")
+        return [
+            {
+                "label": self._label_map[label],
+                "used_fallback": used_fallback,
+                "rules": [(self._label_map[l], literals) for l, literals in rules],
+            }
+            for label, used_fallback, rules in self.rules_.explain(_to_frame(X))
+        ]
+
+    def get_rules(self):
+        """Patterns as dictionaries, with ``coverage`` keyed by class."""
+        check_is_fitted(self, "rules_")
+        classes = [self._label_map[label] for label in self.rules_.get_labels()]
+        return [
+            {
+                "label": self._label_map[p.label],
+                "literals": p.literals,
+                "degree": p.degree,
+                "coverage": dict(zip(classes, p.coverage)),
+                "prevalence": p.prevalence,
+                "homogeneity": p.homogeneity,
+                "index": p.index,
+            }
+            for p in self.rules_.get_rules()
+        ]
@@ -1,22 +1,111 @@
 use std::cmp::Ordering;
+use std::sync::Arc;
 
 use polars::prelude::*;
 
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LadError, LadResult};
+use crate::persistence::{from_values, parse_dtype, to_values, BinarizerModel, CutpointsModel};
+use crate::progress::{Event, Observer};
+
+/// How missing values are binarized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NullPolicy {
+    /// Binary features are false on missing values and every feature that had missing values
+    /// during fitting gets an extra `feature is null` indicator.
+    #[default]
+    Indicator,
+    /// Binary features are null on missing values, so patterns using them neither match nor
+    /// reject the observation.
+    Unknown,
+    /// Observations with a missing value in any column are dropped, see
+    /// [`Binarizer::kept_rows`]. When predicting, [`RuleGenerator`](crate::RuleGenerator) keeps
+    /// them and gives them the fallback label.
+    Drop,
+}
+
+/// How candidate cutpoints are scored, cutpoints scoring below the threshold being dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CutpointCriterion {
+    /// Separation of the label rates on both sides of the cutpoint, between 0 and 1.
+    #[default]
+    Score,
+    /// Decrease of the label entropy, in bits.
+    InformationGain,
+    /// Information gain divided by the entropy of the split itself.
+    GainRatio,
+    /// Decrease of the Gini impurity.
+    Gini,
+    /// Pearson chi-square statistic of the labels against the side of the cutpoint.
+    ChiSquare,
+    /// Fayyad–Irani recursive splitting: the cutpoint with the highest information gain splits
+    /// the rows, and each side is split again, for as long as the gain beats the minimum
+    /// description length cost. Scored by the gain minus the cost, so use a threshold of 0.
+    Mdl,
+}
+
 #[derive(Clone)]
 pub struct Binarizer {
     cutpoints: Vec<Series>,
+    null_features: Vec<String>,
+    /// Levels of every nominal column seen during fitting, named after the column
+    levels: Vec<Series>,
     threshold: f64,
     nominal_size: usize,
     max_cutpoints: usize,
+    intervals: bool,
+    null_policy: NullPolicy,
+    criterion: CutpointCriterion,
+    fitted: bool,
+    observer: Option<Arc<dyn Observer>>,
 }
 
 impl Binarizer {
     pub const fn new(threshold: f64, nominal_size: usize, max_cutpoints_per_column: usize) -> Self {
         Self {
             cutpoints: Vec::new(),
+            null_features: Vec::new(),
+            levels: Vec::new(),
             threshold,
             nominal_size,
             max_cutpoints: max_cutpoints_per_column,
+            intervals: false,
+            null_policy: NullPolicy::Indicator,
+            criterion: CutpointCriterion::Score,
+            fitted: false,
+            observer: None,
+        }
+    }
+
+    /// Also emit `a < feature <= b` features for every pair of consecutive cutpoints.
+    #[must_use]
+    pub const fn with_intervals(mut self, intervals: bool) -> Self {
+        self.intervals = intervals;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_null_policy(mut self, null_policy: NullPolicy) -> Self {
+        self.null_policy = null_policy;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_criterion(mut self, criterion: CutpointCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn emit(&self, event: &Event<'_>) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(event);
         }
     }
 
@@ -24,27 +113,54 @@ impl Binarizer {
         self.cutpoints.clone()
     }
 
+    pub(crate) fn set_cutpoints(&mut self, cutpoints: Vec<Series>) {
+        self.cutpoints = cutpoints;
+    }
+
+    /// Picks the cutpoints of every numeric column. With `weights`, rows count for their weight
+    /// rather than once when cutpoints are scored.
     pub fn generate_cutpoints(
         &mut self,
         data: &DataFrame,
         label: &Series,
-    ) -> Result<(), PolarsError> {
-        if data.shape().0 != label.len() {
-            println!(
-                "Lengths of data {} and label {} do not match",
-                data.shape().0,
-                label.len()
-            );
-            return Ok(());
+        weights: Option<&Series>,
+    ) -> LadResult<()> {
+        if data.height() != label.len() {
+            return Err(LadError::LengthMismatch {
+                data: data.height(),
+                label: label.len(),
+            });
         }
+        let weights = Series::new(
+            "weight".into(),
+            row_weights(weights, data.height())?.unwrap_or_else(|| vec![1.0; data.height()]),
+        );
+        let (data, label, weights) = if self.null_policy == NullPolicy::Drop {
+            let mask = self.kept_rows(data)?;
+            (
+                data.filter(&mask)?,
+                label.filter(&mask)?,
+                weights.filter(&mask)?,
+            )
+        } else {
+            (data.clone(), label.clone(), weights)
+        };
+        let label = &label;
         let schema = data.schema();
         self.cutpoints = Vec::new();
+        self.null_features = data
+            .get_columns()
+            .iter()
+            .filter(|x| x.null_count() > 0)
+            .map(|x| x.name().to_string())
+            .collect();
+        self.levels = Vec::new();
         let unique_labels = label.unique_stable()?;
-        let mut label_counts = vec![0u128; unique_labels.len()];
-        for l in label.iter() {
+        let mut label_counts = vec![0.0; unique_labels.len()];
+        for (l, w) in label.iter().zip(weights.f64()?.into_no_null_iter()) {
             for (j, lj) in unique_labels.iter().enumerate() {
                 if lj == l {
-                    label_counts[j] += 1;
+                    label_counts[j] += w;
                     break;
                 }
             }
@@ -54,46 +170,85 @@ impl Binarizer {
         for (idx, (feature_name, data_type)) in schema.iter().enumerate() {
             let column = data[idx].clone();
             let a = column.n_unique().unwrap_or_default();
+            if !data_type.is_bool()
+                && (a <= self.nominal_size
+                    || data_type.is_string()
+                    || data_type.is_categorical()
+                    || data_type.is_enum())
+            {
+                self.levels.push(column.unique_stable()?.drop_nulls());
+            }
             if a <= self.nominal_size {
                 continue;
             }
             if data_type.is_numeric() {
-                let mut column_and_label = DataFrame::new(vec![label.clone(), column])?;
-                let mut running_counts = vec![0u128; unique_labels.len()];
+                let mut column_and_label = DataFrame::new(vec![
+                    label.clone().with_name("label".into()),
+                    column.with_name("value".into()),
+                    weights.clone(),
+                ])?;
+                let mut running_counts = vec![0.0; unique_labels.len()];
                 column_and_label = column_and_label
-                    .sort([feature_name.to_string()], SortMultipleOptions::default())?;
+                    .drop_nulls(Some(&["value".to_string()]))?
+                    .sort(["value"], SortMultipleOptions::default())?;
+                if column_and_label.height() == 0 {
+                    self.cutpoints
+                        .push(Series::new_empty(feature_name.clone(), data_type));
+                    continue;
+                }
                 let mut cps = Vec::new();
-                let sorted = column_and_label.drop_in_place(feature_name.as_ref())?;
-                let labels = column_and_label.drop_in_place(label.name().as_ref())?;
+                // Label counts below every candidate cutpoint, split recursively under Mdl
+                let mut boundaries = Vec::new();
+                let sorted = column_and_label.drop_in_place("value")?;
+                let labels = column_and_label.drop_in_place("label")?;
+                let row_weights = column_and_label.drop_in_place("weight")?;
+                let row_weights = row_weights.f64()?;
                 let mut prev_label = labels.get(0)?;
                 let mut prev_value = sorted.get(0)?;
                 running_counts[unsafe {
-                    labels
+                    unique_labels
                         .iter()
                         .position(|x| x == prev_label)
                         .unwrap_unchecked()
-                }] += 1;
-                for (s, l) in sorted.iter().zip(labels.iter()).skip(1) {
-                    let score = Self::score(&running_counts, &label_counts);
+                }] += row_weights.get(0).unwrap_or_default();
+                for ((s, l), w) in sorted
+                    .iter()
+                    .zip(labels.iter())
+                    .zip(row_weights.into_no_null_iter())
+                    .skip(1)
+                {
+                    let left = running_counts.clone();
+                    let score = self.evaluate(&left, &label_counts);
                     running_counts[unsafe {
                         unique_labels.iter().position(|x| x == l).unwrap_unchecked()
-                    }] += 1;
+                    }] += w;
                     if prev_label != l && prev_value != s {
-                        if score >= self.threshold {
-                            cps.push((
-                                AnyValue::from(unsafe {
-                                    Series::new("tmp".into(), [s.clone(), prev_value.clone()])
-                                        .mean()
-                                        .unwrap_unchecked()
-                                })
-                                .cast(data_type),
+                        let cutpoint = AnyValue::from(unsafe {
+                            Series::new("tmp".into(), [s.clone(), prev_value.clone()])
+                                .mean()
+                                .unwrap_unchecked()
+                        })
+                        .cast(data_type);
+                        if self.criterion == CutpointCriterion::Mdl {
+                            boundaries.push((cutpoint, left));
+                        } else {
+                            self.emit(&Event::CutpointScored {
+                                feature: feature_name,
+                                cutpoint: &cutpoint,
                                 score,
-                            ));
+                            });
+                            if score >= self.threshold {
+                                cps.push((cutpoint, score));
+                            }
                         }
                         prev_value = s;
                         prev_label = l;
                     }
                 }
+                if self.criterion == CutpointCriterion::Mdl {
+                    let lower = vec![0.0; unique_labels.len()];
+                    self.split(feature_name, &boundaries, &lower, &running_counts, &mut cps);
+                }
                 cps.sort_by(|(_, a), (_, b)| {
                     if a.is_nan() && b.is_nan() {
                         Ordering::Equal
@@ -108,71 +263,179 @@ impl Binarizer {
                 let cps = cps
                     .iter()
                     .rev()
-                    .map(|(x, s)| {
-                        print!("{s} ");
-                        x.clone()
-                    })
+                    .map(|(x, _)| x.clone())
                     .take(self.max_cutpoints)
                     .collect::<Vec<_>>();
-                println!();
                 self.cutpoints.push(Series::new(feature_name.clone(), cps));
             }
         }
+        self.fitted = true;
         Ok(())
     }
 
-    pub fn transform(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+    pub fn transform(&self, df: &DataFrame) -> LadResult<DataFrame> {
+        if !self.fitted {
+            return Err(LadError::NotFitted("Binarizer"));
+        }
+        if self.null_policy == NullPolicy::Drop {
+            return self.binarize(&df.filter(&self.kept_rows(df)?)?);
+        }
+        self.binarize(df)
+    }
+
+    /// Binarizes like [`transform`](Self::transform) but keeps every row, the rows it drops being
+    /// missing on every binary feature so that no pattern covers them.
+    pub(crate) fn transform_all(&self, df: &DataFrame) -> LadResult<DataFrame> {
+        if self.null_policy != NullPolicy::Drop {
+            return self.transform(df);
+        }
+        let kept = self.kept_rows(df)?;
+        let binary = self.transform(df)?;
+        let mut next = 0;
+        let rows = kept
+            .into_iter()
+            .map(|x| {
+                x.unwrap_or(false).then(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect::<IdxCa>();
+        Ok(binary.take(&rows)?)
+    }
+
+    /// Rows [`transform`](Self::transform) keeps, which are the rows without missing values under
+    /// [`NullPolicy::Drop`] and all rows otherwise. Use it to filter the labels of dropped rows.
+    pub fn kept_rows(&self, df: &DataFrame) -> LadResult<BooleanChunked> {
+        let mut mask = BooleanChunked::full("kept".into(), true, df.height());
+        if self.null_policy == NullPolicy::Drop {
+            for column in df.get_columns() {
+                mask = &mask & &column.is_not_null();
+            }
+        }
+        Ok(mask)
+    }
+
+    fn binarize(&self, df: &DataFrame) -> LadResult<DataFrame> {
         let schema = df.schema();
 
         let mut out = DataFrame::default();
 
         for (feature_name, data_type) in schema.iter() {
             let column = df.column(feature_name)?;
+            // Categories of different series cannot be compared, their text can
+            let text;
+            let column = if data_type.is_categorical() || data_type.is_enum() {
+                text = column.cast(&DataType::String)?;
+                &text
+            } else {
+                column
+            };
             if data_type.is_bool() {
-                out.hstack_mut(&[column.clone()])?;
-                continue;
-            }
-            let a = column.n_unique()?;
-            if false {
-                // set to false to debug meant to be "a == 2"
-                for value in column.iter().take(1) {
-                    out.hstack_mut(&[Series::new(
-                        format!("{feature_name} = {value}").into(),
-                        column.iter().map(|x| x == value).collect::<Vec<_>>(),
-                    )])?;
+                if self.null_policy == NullPolicy::Unknown {
+                    out.hstack_mut(std::slice::from_ref(column))?;
+                } else {
+                    out.hstack_mut(&[column.bool()?.fill_null_with_values(false)?.into_series()])?;
                 }
-            } else if a <= self.nominal_size || data_type.is_string() {
-                for value in column.unique_stable()?.iter() {
-                    out.hstack_mut(&[Series::new(
-                        format!("{feature_name} = {value}").into(),
-                        column.iter().map(|x| x == value).collect::<Vec<_>>(),
+            } else if let Some(levels) = self.levels(column)? {
+                for value in levels.iter().filter(|x| !x.is_null()) {
+                    out.hstack_mut(&[self.feature(
+                        format!("{feature_name} = {value}"),
+                        column,
+                        |x| x == &value,
                     )])?;
                 }
             } else if data_type.is_numeric() {
                 let Some(cutpoints) = self.cutpoints.iter().find(|x| x.name() == feature_name)
                 else {
-                    return Err(PolarsError::ColumnNotFound(format!("Cannot find numeric column {feature_name}.\nMake sure schema of input and output data is the same.").into()));
+                    return Err(LadError::ColumnNotFound(feature_name.to_string()));
                 };
                 for cutpoint in cutpoints.iter() {
-                    out.hstack_mut(&[Series::new(
-                        format!("{feature_name} > {cutpoint}").into(),
-                        column.iter().map(|x| x > cutpoint).collect::<Vec<_>>(),
+                    out.hstack_mut(&[self.feature(
+                        format!("{feature_name} > {cutpoint}"),
+                        column,
+                        |x| x > &cutpoint,
                     )])?;
                 }
+                if self.intervals {
+                    let sorted = cutpoints.sort(SortOptions::default())?;
+                    for (low, high) in sorted.iter().zip(sorted.iter().skip(1)) {
+                        out.hstack_mut(&[self.feature(
+                            format!("{low} < {feature_name} <= {high}"),
+                            column,
+                            |x| x > &low && x <= &high,
+                        )])?;
+                    }
+                }
             } else {
-                println!("{data_type} not supported yet. Skipping");
+                return Err(LadError::UnsupportedDtype {
+                    column: feature_name.to_string(),
+                    dtype: data_type.to_string(),
+                });
+            }
+            if self.null_policy == NullPolicy::Indicator
+                && self
+                    .null_features
+                    .iter()
+                    .any(|x| x == feature_name.as_str())
+            {
+                out.hstack_mut(&[column
+                    .is_null()
+                    .with_name(format!("{feature_name} is null").into())
+                    .into_series()])?;
             }
         }
 
         Ok(out)
     }
 
-    fn score(runner: &[u128], total: &[u128]) -> f64 {
-        #[allow(clippy::cast_precision_loss)]
+    /// Levels of `column` if it is nominal, the ones seen during fitting. Columns the binarizer
+    /// was not fitted on are nominal if they are strings or have few values, and take their own
+    /// levels.
+    fn levels(&self, column: &Series) -> LadResult<Option<Series>> {
+        if let Some(levels) = self.levels.iter().find(|x| x.name() == column.name()) {
+            return Ok(Some(levels.cast(column.dtype())?));
+        }
+        if self.cutpoints.iter().any(|x| x.name() == column.name()) {
+            return Ok(None);
+        }
+        if column.n_unique()? <= self.nominal_size || column.dtype().is_string() {
+            return Ok(Some(column.unique_stable()?));
+        }
+        Ok(None)
+    }
+
+    /// Binary feature `name` holding `predicate` on every non missing value of `column`.
+    fn feature(
+        &self,
+        name: String,
+        column: &Series,
+        predicate: impl Fn(&AnyValue) -> bool,
+    ) -> Series {
+        if self.null_policy == NullPolicy::Unknown {
+            Series::new(
+                name.into(),
+                column
+                    .iter()
+                    .map(|x| (!x.is_null()).then(|| predicate(&x)))
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            Series::new(
+                name.into(),
+                column
+                    .iter()
+                    .map(|x| !x.is_null() && predicate(&x))
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    fn score(runner: &[f64], total: &[f64]) -> f64 {
         let rates = runner
             .iter()
             .zip(total.iter())
-            .map(|(&r, &t)| r as f64 / t as f64)
+            .map(|(&r, &t)| r / t)
             .collect::<Vec<_>>();
         let sum = rates.iter().sum::<f64>();
 
@@ -186,33 +449,355 @@ impl Binarizer {
         x / k.mul_add(1.0 - x, 1.0)
     }
 
-    fn entropy(runner: &[u128], total: &[u128]) -> f64 {
-        // Calculate total counts
-        let total_count: u128 = total.iter().sum();
+    pub(crate) fn to_model(&self) -> LadResult<BinarizerModel> {
+        let cutpoints = self
+            .cutpoints
+            .iter()
+            .map(|x| {
+                Ok(CutpointsModel {
+                    feature: x.name().to_string(),
+                    dtype: x.dtype().to_string(),
+                    values: x
+                        .cast(&DataType::Float64)?
+                        .f64()?
+                        .into_no_null_iter()
+                        .collect(),
+                })
+            })
+            .collect::<LadResult<Vec<_>>>()?;
+        let levels = self
+            .levels
+            .iter()
+            .map(to_values)
+            .collect::<LadResult<Vec<_>>>()?;
+        Ok(BinarizerModel {
+            threshold: self.threshold,
+            nominal_size: self.nominal_size,
+            max_cutpoints: self.max_cutpoints,
+            intervals: self.intervals,
+            null_policy: self.null_policy,
+            criterion: self.criterion,
+            null_features: self.null_features.clone(),
+            levels,
+            fitted: self.fitted,
+            cutpoints,
+        })
+    }
+
+    pub(crate) fn from_model(model: BinarizerModel) -> LadResult<Self> {
+        let cutpoints = model
+            .cutpoints
+            .into_iter()
+            .map(|x| {
+                let dtype = parse_dtype(&x.dtype)?;
+                Ok(Series::new(x.feature.into(), x.values).cast(&dtype)?)
+            })
+            .collect::<LadResult<Vec<_>>>()?;
+        let levels = model
+            .levels
+            .iter()
+            .map(from_values)
+            .collect::<LadResult<Vec<_>>>()?;
+        Ok(Self {
+            cutpoints,
+            null_features: model.null_features,
+            levels,
+            threshold: model.threshold,
+            nominal_size: model.nominal_size,
+            max_cutpoints: model.max_cutpoints,
+            intervals: model.intervals,
+            null_policy: model.null_policy,
+            criterion: model.criterion,
+            fitted: model.fitted,
+            observer: None,
+        })
+    }
 
-        // Calculate probabilities
-        let probabilities: Vec<f64> = runner
+    /// Score of a cutpoint under the criterion, `left` weighing the rows of every label below
+    /// the cutpoint and `total` all the rows of every label.
+    fn evaluate(&self, left: &[f64], total: &[f64]) -> f64 {
+        let right = total
             .iter()
-            .map(|&r| {
-                if total_count > 0 {
-                    r as f64 / total_count as f64
+            .zip(left)
+            .map(|(t, l)| (t - l).max(0.0))
+            .collect::<Vec<_>>();
+        let sizes = [left.iter().sum::<f64>(), right.iter().sum::<f64>()];
+        let (n_left, n_right, n) = (sizes[0], sizes[1], sizes[0] + sizes[1]);
+        let information_gain = || Self::information_gain(left, &right, total);
+        match self.criterion {
+            CutpointCriterion::Score => Self::score(left, total),
+            CutpointCriterion::InformationGain => information_gain(),
+            CutpointCriterion::GainRatio => {
+                let split = Self::entropy(&sizes);
+                if split > 0.0 {
+                    information_gain() / split
                 } else {
                     0.0
                 }
+            }
+            CutpointCriterion::Gini => {
+                Self::gini(total) - (n_left * Self::gini(left) + n_right * Self::gini(&right)) / n
+            }
+            CutpointCriterion::ChiSquare => {
+                let mut chi = 0.0;
+                for (side, size) in [(left, n_left), (right.as_slice(), n_right)] {
+                    for (&observed, &t) in side.iter().zip(total) {
+                        let expected = size * t / n;
+                        if expected > 0.0 {
+                            let diff = observed - expected;
+                            chi += diff * diff / expected;
+                        }
+                    }
+                }
+                chi
+            }
+            CutpointCriterion::Mdl => {
+                #[allow(clippy::cast_precision_loss)]
+                let classes = |x: &[f64]| x.iter().filter(|&&c| c > 0.0).count() as f64;
+                let (k, k_left, k_right) = (classes(total), classes(left), classes(&right));
+                let delta = (3f64.powf(k) - 2.0).log2()
+                    - (k * Self::entropy(total)
+                        - k_left * Self::entropy(left)
+                        - k_right * Self::entropy(&right));
+                information_gain() - ((n - 1.0).log2() + delta) / n
+            }
+        }
+    }
+
+    /// Splits the rows between the label counts `lower` and `upper` at the boundary with the
+    /// highest information gain, if its Mdl score reaches the threshold, then splits both sides
+    /// the same way. Each boundary comes with the label counts of all the rows below it.
+    fn split<'a>(
+        &self,
+        feature: &str,
+        boundaries: &[(AnyValue<'a>, Vec<f64>)],
+        lower: &[f64],
+        upper: &[f64],
+        cps: &mut Vec<(AnyValue<'a>, f64)>,
+    ) {
+        let total = upper
+            .iter()
+            .zip(lower)
+            .map(|(u, l)| u - l)
+            .collect::<Vec<_>>();
+        let below = |counts: &[f64]| {
+            counts
+                .iter()
+                .zip(lower)
+                .map(|(c, l)| c - l)
+                .collect::<Vec<_>>()
+        };
+        let above = |counts: &[f64]| {
+            upper
+                .iter()
+                .zip(counts)
+                .map(|(u, c)| u - c)
+                .collect::<Vec<_>>()
+        };
+        let Some((best, _)) = boundaries
+            .iter()
+            .enumerate()
+            .map(|(i, (_, counts))| {
+                let gain = Self::information_gain(&below(counts), &above(counts), &total);
+                (i, gain)
             })
-            .collect();
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            return;
+        };
+        let (cutpoint, counts) = &boundaries[best];
+        let score = self.evaluate(&below(counts), &total);
+        self.emit(&Event::CutpointScored {
+            feature,
+            cutpoint,
+            score,
+        });
+        if score.is_nan() || score < self.threshold {
+            return;
+        }
+        cps.push((cutpoint.clone(), score));
+        self.split(feature, &boundaries[..best], lower, counts, cps);
+        self.split(feature, &boundaries[best + 1..], counts, upper, cps);
+    }
 
-        let entropy_value: f64 = probabilities
+    /// Decrease of the entropy of the label distribution `total` when split into `left` and
+    /// `right`.
+    fn information_gain(left: &[f64], right: &[f64], total: &[f64]) -> f64 {
+        let (n_left, n_right) = (left.iter().sum::<f64>(), right.iter().sum::<f64>());
+        let n = n_left + n_right;
+        Self::entropy(total) - (n_left * Self::entropy(left) + n_right * Self::entropy(right)) / n
+    }
+
+    /// Shannon entropy in bits of the label distribution `counts`.
+    fn entropy(counts: &[f64]) -> f64 {
+        let total = counts.iter().sum::<f64>();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        counts
             .iter()
-            .filter_map(|&p| {
-                if p > 0.0 {
-                    Some(-p * p.log2()) // Using natural logarithm
-                } else {
-                    None
-                }
+            .filter(|&&c| c > 0.0)
+            .map(|&c| {
+                let p = c / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Gini impurity of the label distribution `counts`.
+    fn gini(counts: &[f64]) -> f64 {
+        let total = counts.iter().sum::<f64>();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        1.0 - counts
+            .iter()
+            .map(|&c| {
+                let p = c / total;
+                p * p
             })
-            .sum();
+            .sum::<f64>()
+    }
+}
+
+/// Row weights as floats, checking there is one finite and non-negative weight per row.
+pub(crate) fn row_weights(weights: Option<&Series>, height: usize) -> LadResult<Option<Vec<f64>>> {
+    let Some(weights) = weights else {
+        return Ok(None);
+    };
+    if weights.len() != height {
+        return Err(LadError::InvalidWeights(format!(
+            "{} weights for {height} rows",
+            weights.len()
+        )));
+    }
+    if !weights.dtype().is_numeric() {
+        return Err(LadError::InvalidWeights(format!(
+            "weights have data type {}",
+            weights.dtype()
+        )));
+    }
+    let weights = weights.cast(&DataType::Float64)?;
+    weights
+        .f64()?
+        .into_iter()
+        .map(|x| match x {
+            Some(x) if x.is_finite() && x >= 0.0 => Ok(x),
+            Some(x) => Err(LadError::InvalidWeights(format!(
+                "weight {x} is not allowed"
+            ))),
+            None => Err(LadError::InvalidWeights(
+                "weights have missing values".into(),
+            )),
+        })
+        .collect::<LadResult<Vec<_>>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cutpoints of the only column of `data`.
+    fn cutpoints(binarizer: &mut Binarizer, data: &DataFrame, label: &Series) -> Vec<f64> {
+        binarizer.generate_cutpoints(data, label, None).unwrap();
+        let mut cutpoints = binarizer.get_cutpoints()[0]
+            .cast(&DataType::Float64)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>();
+        cutpoints.sort_by(f64::total_cmp);
+        cutpoints
+    }
+
+    #[test]
+    fn mdl_splits_recursively() {
+        // 40 rows labelled a, 10 labelled b then 10 labelled a: on all rows only the first cut
+        // pays for itself, the second one does on the rows right of the first. Blocks of 10
+        // are too few to pay for any cut.
+        for (sizes, expected) in [([40, 10, 10], 2), ([10, 10, 10], 0)] {
+            let label = sizes
+                .iter()
+                .zip(["a", "b", "a"])
+                .flat_map(|(&size, label)| vec![label; size])
+                .collect::<Vec<_>>();
+            let x = (0..label.len()).map(|x| x as f64).collect::<Vec<_>>();
+            let data = DataFrame::new(vec![Series::new("x".into(), x)]).unwrap();
+            let label = Series::new("label".into(), label);
+            let mut binarizer = Binarizer::new(0.0, 0, 10).with_criterion(CutpointCriterion::Mdl);
+            let cutpoints = cutpoints(&mut binarizer, &data, &label);
+            assert_eq!(cutpoints.len(), expected, "{sizes:?}: {cutpoints:?}");
+        }
+    }
+
+    #[test]
+    fn intervals_lie_between_consecutive_cutpoints() {
+        let x = (0..30).map(|i| f64::from(i % 3)).collect::<Vec<_>>();
+        let label = x
+            .iter()
+            .map(|&x| if x == 1.0 { "b" } else { "a" })
+            .collect::<Vec<_>>();
+        let data = DataFrame::new(vec![Series::new("x".into(), &x)]).unwrap();
+        let label = Series::new("label".into(), label);
+        let mut binarizer = Binarizer::new(f64::NEG_INFINITY, 0, 10).with_intervals(true);
+        assert_eq!(cutpoints(&mut binarizer, &data, &label), [0.5, 1.5]);
+        let binary = binarizer.transform(&data).unwrap();
+        let interval = binary.column("0.5 < x <= 1.5").unwrap().bool().unwrap();
+        for (x, inside) in x.iter().zip(interval.into_no_null_iter()) {
+            assert_eq!(inside, *x == 1.0, "{x}");
+        }
+        let without = binarizer.clone().with_intervals(false).transform(&data);
+        assert_eq!(without.unwrap().width(), binary.width() - 1);
+    }
 
-        entropy_value
+    /// Highest score of a cutpoint of every feature.
+    #[derive(Default)]
+    struct BestScores(std::sync::Mutex<std::collections::HashMap<String, f64>>);
+
+    impl Observer for BestScores {
+        fn on_event(&self, event: &Event<'_>) {
+            if let Event::CutpointScored { feature, score, .. } = event {
+                let mut scores = self.0.lock().unwrap();
+                let best = scores
+                    .entry(feature.to_string())
+                    .or_insert(f64::NEG_INFINITY);
+                *best = best.max(*score);
+            }
+        }
+    }
+
+    #[test]
+    fn criteria_prefer_cutpoints_separating_the_labels() {
+        // x is the label, z disagrees with it on every fifth row
+        let x = (0..40).map(|i| f64::from(i % 2)).collect::<Vec<_>>();
+        let z = (0..40)
+            .map(|i| f64::from((i % 2) ^ u8::from(i % 5 == 0)))
+            .collect::<Vec<_>>();
+        let label = x
+            .iter()
+            .map(|&x| if x == 1.0 { "b" } else { "a" })
+            .collect::<Vec<_>>();
+        let data =
+            DataFrame::new(vec![Series::new("x".into(), x), Series::new("z".into(), z)]).unwrap();
+        let label = Series::new("label".into(), label);
+        for criterion in [
+            CutpointCriterion::Score,
+            CutpointCriterion::InformationGain,
+            CutpointCriterion::GainRatio,
+            CutpointCriterion::Gini,
+            CutpointCriterion::ChiSquare,
+            CutpointCriterion::Mdl,
+        ] {
+            let scores = Arc::new(BestScores::default());
+            let mut binarizer = Binarizer::new(f64::NEG_INFINITY, 0, 10)
+                .with_criterion(criterion)
+                .with_observer(scores.clone());
+            binarizer.generate_cutpoints(&data, &label, None).unwrap();
+            let scores = scores.0.lock().unwrap();
+            let score = |feature| scores.get(feature).copied().unwrap_or(f64::NEG_INFINITY);
+            assert!(score("x") > score("z"), "{criterion:?}: {scores:?}");
+        }
     }
 }
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use polars::prelude::*;
+
+use crate::error::{LadError, LadResult};
+
+/// Set of row indices packed into `u64` words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Bitset {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitset {
+    pub(crate) fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub(crate) fn ones(len: usize) -> Self {
+        let mut set = Self {
+            words: vec![u64::MAX; len.div_ceil(64)],
+            len,
+        };
+        set.clear_tail();
+        set
+    }
+
+    pub(crate) fn from_bools(values: impl IntoIterator<Item = bool>) -> Self {
+        let mut set = Self::zeros(0);
+        for (i, value) in values.into_iter().enumerate() {
+            if i % 64 == 0 {
+                set.words.push(0);
+            }
+            set.words[i / 64] |= u64::from(value) << (i % 64);
+            set.len = i + 1;
+        }
+        set
+    }
+
+    pub(crate) fn contains(&self, i: usize) -> bool {
+        i < self.len && self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.words.iter().map(|x| x.count_ones() as usize).sum()
+    }
+
+    /// Size of the intersection, without materializing it.
+    pub(crate) fn and_count(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Sum of `weights` over the elements, their number without weights.
+    pub(crate) fn weight(&self, weights: Option<&[f64]>) -> f64 {
+        match weights {
+            #[allow(clippy::cast_precision_loss)]
+            None => self.count() as f64,
+            Some(weights) => self.iter_ones().map(|i| weights[i]).sum(),
+        }
+    }
+
+    /// Weight of the intersection, without materializing it.
+    pub(crate) fn and_weight(&self, other: &Self, weights: Option<&[f64]>) -> f64 {
+        let Some(weights) = weights else {
+            #[allow(clippy::cast_precision_loss)]
+            return self.and_count(other) as f64;
+        };
+        let mut total = 0.0;
+        for (i, (a, b)) in self.words.iter().zip(&other.words).enumerate() {
+            let mut word = a & b;
+            while word != 0 {
+                total += weights[i * 64 + word.trailing_zeros() as usize];
+                word &= word - 1;
+            }
+        }
+        total
+    }
+
+    pub(crate) fn and_assign(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+
+    pub(crate) fn or_assign(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Removes every element of `other`.
+    pub(crate) fn and_not_assign(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= !b;
+        }
+    }
+
+    pub(crate) fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    fn clear_tail(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+}
+
+/// Binarized data packed column by column, so that the coverage of a pattern is the AND of its
+/// literals.
+pub(crate) struct BitMatrix {
+    height: usize,
+    index: HashMap<String, usize>,
+    /// Rows where the column is true and rows where it is false, missing values are in neither.
+    literals: Vec<[Bitset; 2]>,
+}
+
+impl BitMatrix {
+    pub(crate) fn new(data: &DataFrame) -> LadResult<Self> {
+        let mut index = HashMap::new();
+        let mut literals = Vec::with_capacity(data.width());
+        for (i, column) in data.get_columns().iter().enumerate() {
+            let column = column.cast(&DataType::Boolean)?;
+            let values = column.bool()?;
+            literals.push([
+                Bitset::from_bools(values.into_iter().map(|x| x == Some(false))),
+                Bitset::from_bools(values.into_iter().map(|x| x == Some(true))),
+            ]);
+            index.insert(column.name().to_string(), i);
+        }
+        Ok(Self {
+            height: data.height(),
+            index,
+            literals,
+        })
+    }
+
+    pub(crate) const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub(crate) fn literal(&self, value: bool, column: &str) -> LadResult<&Bitset> {
+        let Some(&i) = self.index.get(column) else {
+            return Err(LadError::ColumnNotFound(column.to_string()));
+        };
+        Ok(&self.literals[i][usize::from(value)])
+    }
+
+    /// Value of `column` on `row`, `None` if it is missing.
+    pub(crate) fn value(&self, column: &str, row: usize) -> LadResult<Option<bool>> {
+        if self.literal(true, column)?.contains(row) {
+            Ok(Some(true))
+        } else if self.literal(false, column)?.contains(row) {
+            Ok(Some(false))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Rows satisfying every literal of `pattern`.
+    pub(crate) fn coverage<'a>(
+        &self,
+        pattern: impl IntoIterator<Item = &'a (bool, String)>,
+    ) -> LadResult<Bitset> {
+        let mut covered = Bitset::ones(self.height);
+        for (value, column) in pattern {
+            covered.and_assign(self.literal(*value, column)?);
+        }
+        Ok(covered)
+    }
+}
@@ -0,0 +1,299 @@
+//! Branch and bound search for the pattern of largest coverage.
+//!
+//! This solves the integer program of LAD pattern generation, which picks literals so as to
+//! cover as many positive rows as possible while the covered rows stay homogeneous, by searching
+//! the literals directly instead of going through a relaxation.
+
+use std::collections::BTreeSet;
+
+use super::bitset::{BitMatrix, Bitset};
+use crate::error::LadResult;
+
+/// Literals of a pattern and the rows it covers.
+type Found = (BTreeSet<(bool, String)>, Bitset);
+
+/// Pattern of at most `max_degree` literals covering the most weight of `positive`, and at least
+/// `min_rows` of its rows, such that positive rows make up at least `min_purity` of the weight of
+/// the covered rows of `positive` and `negative`. Rows weigh one without `weights`.
+///
+/// Once `stop` returns true the search ends with the best pattern found so far.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve(
+    data: &BitMatrix,
+    features: &[String],
+    positive: &Bitset,
+    negative: &Bitset,
+    weights: Option<&[f64]>,
+    max_degree: usize,
+    min_rows: usize,
+    min_purity: f64,
+    stop: &dyn Fn() -> bool,
+) -> LadResult<Option<Found>> {
+    let mut search = Search {
+        data,
+        features,
+        positive,
+        negative,
+        weights,
+        max_degree,
+        min_rows,
+        min_purity,
+        stop,
+        used: vec![false; features.len()],
+        forbidden: vec![[false; 2]; features.len()],
+        chosen: Vec::new(),
+        best: None,
+        best_weight: 0.0,
+    };
+    search.run(Bitset::ones(data.height()))?;
+    Ok(search.best.map(|(chosen, covered)| {
+        let literals = chosen
+            .into_iter()
+            .map(|(value, c)| (value, features[c].clone()))
+            .collect();
+        (literals, covered)
+    }))
+}
+
+struct Search<'a> {
+    data: &'a BitMatrix,
+    features: &'a [String],
+    positive: &'a Bitset,
+    negative: &'a Bitset,
+    weights: Option<&'a [f64]>,
+    max_degree: usize,
+    min_rows: usize,
+    min_purity: f64,
+    stop: &'a dyn Fn() -> bool,
+    /// Columns with a chosen literal
+    used: Vec<bool>,
+    /// Literals explored in an earlier sibling branch, by column and value
+    forbidden: Vec<[bool; 2]>,
+    chosen: Vec<(bool, usize)>,
+    best: Option<(Vec<(bool, usize)>, Bitset)>,
+    /// Positive weight covered by the best pattern, patterns must beat it to be kept
+    best_weight: f64,
+}
+
+impl Search<'_> {
+    fn run(&mut self, covered: Bitset) -> LadResult<()> {
+        if (self.stop)() {
+            return Ok(());
+        }
+        // Adding literals never covers more rows, so the positive coverage is an upper bound.
+        let weight = covered.and_weight(self.positive, self.weights);
+        if weight <= self.best_weight || covered.and_count(self.positive) < self.min_rows {
+            return Ok(());
+        }
+        let conflicts = covered.and_weight(self.negative, self.weights);
+        if !self.chosen.is_empty()
+            && (conflicts == 0.0 || weight / (weight + conflicts) >= self.min_purity)
+        {
+            self.best_weight = weight;
+            self.best = Some((self.chosen.clone(), covered));
+            return Ok(());
+        }
+        if self.chosen.len() >= self.max_degree {
+            return Ok(());
+        }
+
+        // Homogeneous patterns must exclude every covered negative row of positive weight, so it
+        // is enough to branch on the literals excluding one of them. Relaxed purity needs all
+        // literals.
+        let conflict = if self.min_purity >= 1.0 {
+            covered.iter_ones().find(|&row| {
+                self.negative.contains(row) && self.weights.is_none_or(|x| x[row] > 0.0)
+            })
+        } else {
+            None
+        };
+        let mut children = Vec::new();
+        for (c, feature) in self.features.iter().enumerate() {
+            if self.used[c] {
+                continue;
+            }
+            let excluded = match conflict {
+                Some(row) => self.data.value(feature, row)?,
+                None => None,
+            };
+            for value in [true, false] {
+                if excluded == Some(value) || self.forbidden[c][usize::from(value)] {
+                    continue;
+                }
+                let mut child = covered.clone();
+                child.and_assign(self.data.literal(value, feature)?);
+                let n = child.and_weight(self.positive, self.weights);
+                if n > self.best_weight && child.and_count(self.positive) >= self.min_rows {
+                    children.push((n, value, c, child));
+                }
+            }
+        }
+        // Most promising literals first, so good patterns bound the search early
+        children.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut tried = Vec::new();
+        for (_, value, c, child) in children {
+            self.chosen.push((value, c));
+            self.used[c] = true;
+            self.run(child)?;
+            self.used[c] = false;
+            self.chosen.pop();
+            self.forbidden[c][usize::from(value)] = true;
+            tried.push((value, c));
+        }
+        for (value, c) in tried {
+            self.forbidden[c][usize::from(value)] = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+
+    use super::*;
+    use crate::binarization::Random;
+
+    /// Random binary columns with some missing values, and a random split of the rows into
+    /// positive and negative ones.
+    fn instance(
+        random: &mut Random,
+        rows: usize,
+        columns: usize,
+    ) -> (BitMatrix, Vec<String>, Bitset) {
+        let features = (0..columns).map(|c| format!("f{c}")).collect::<Vec<_>>();
+        let data = DataFrame::new(
+            features
+                .iter()
+                .map(|name| {
+                    let values = (0..rows)
+                        .map(|_| match random.below(10) {
+                            0 => None,
+                            x => Some(x % 2 == 0),
+                        })
+                        .collect::<Vec<_>>();
+                    Series::new(name.into(), values)
+                })
+                .collect(),
+        )
+        .unwrap();
+        let positive = Bitset::from_bools((0..rows).map(|_| random.below(2) == 0));
+        (BitMatrix::new(&data).unwrap(), features, positive)
+    }
+
+    /// Positive weight covered by the best pattern, trying every assignment of at most
+    /// `max_degree` columns.
+    #[allow(clippy::too_many_arguments)]
+    fn brute_force(
+        data: &BitMatrix,
+        features: &[String],
+        positive: &Bitset,
+        negative: &Bitset,
+        weights: Option<&[f64]>,
+        max_degree: usize,
+        min_rows: usize,
+        min_purity: f64,
+    ) -> f64 {
+        let mut best = 0.0;
+        // Every column is left out, true or false
+        for code in 0..3usize.pow(features.len() as u32) {
+            let mut pattern = Vec::new();
+            let mut rest = code;
+            for feature in features {
+                match rest % 3 {
+                    1 => pattern.push((true, feature.clone())),
+                    2 => pattern.push((false, feature.clone())),
+                    _ => {}
+                }
+                rest /= 3;
+            }
+            if pattern.is_empty() || pattern.len() > max_degree {
+                continue;
+            }
+            let covered = data.coverage(&pattern).unwrap();
+            let weight = covered.and_weight(positive, weights);
+            let conflicts = covered.and_weight(negative, weights);
+            if weight > best
+                && covered.and_count(positive) >= min_rows
+                && (conflicts == 0.0 || weight / (weight + conflicts) >= min_purity)
+            {
+                best = weight;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn solve_matches_brute_force() {
+        let mut random = Random(7);
+        for case in 0..300 {
+            let (data, features, positive) = instance(&mut random, 12, 4);
+            let mut negative = Bitset::ones(data.height());
+            negative.and_not_assign(&positive);
+            // Halves add up exactly, and zero weights exercise rows that do not count
+            let weights = (case % 2 == 1).then(|| {
+                (0..data.height())
+                    .map(|_| random.below(5) as f64 / 2.0)
+                    .collect::<Vec<_>>()
+            });
+            let weights = weights.as_deref();
+            let max_degree = 1 + random.below(4) as usize;
+            let min_rows = 1 + random.below(3) as usize;
+            let min_purity = [1.0, 0.8, 0.6][random.below(3) as usize];
+
+            let found = solve(
+                &data,
+                &features,
+                &positive,
+                &negative,
+                weights,
+                max_degree,
+                min_rows,
+                min_purity,
+                &|| false,
+            )
+            .unwrap();
+            let best = brute_force(
+                &data, &features, &positive, &negative, weights, max_degree, min_rows, min_purity,
+            );
+            let Some((literals, covered)) = found else {
+                assert_eq!(best, 0.0, "case {case} found no pattern");
+                continue;
+            };
+            assert_eq!(covered, data.coverage(&literals).unwrap(), "case {case}");
+            assert!(
+                !literals.is_empty() && literals.len() <= max_degree,
+                "case {case}"
+            );
+            let weight = covered.and_weight(&positive, weights);
+            let conflicts = covered.and_weight(&negative, weights);
+            assert!(covered.and_count(&positive) >= min_rows, "case {case}");
+            assert!(
+                conflicts == 0.0 || weight / (weight + conflicts) >= min_purity,
+                "case {case}"
+            );
+            assert_eq!(weight, best, "case {case}");
+        }
+    }
+
+    #[test]
+    fn solve_stops_when_asked() {
+        let (data, features, positive) = instance(&mut Random(3), 12, 4);
+        let mut negative = Bitset::ones(data.height());
+        negative.and_not_assign(&positive);
+        let found = solve(
+            &data,
+            &features,
+            &positive,
+            &negative,
+            None,
+            4,
+            1,
+            1.0,
+            &|| true,
+        )
+        .unwrap();
+        assert!(found.is_none());
+    }
+}
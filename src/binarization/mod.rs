@@ -1,2 +1,23 @@
 pub mod binarize;
+pub(crate) mod bitset;
+pub(crate) mod max_coverage;
+pub mod pattern;
 pub mod rule_generation;
+pub(crate) mod set_cover;
+pub mod support_set;
+
+/// Deterministic pseudo random numbers for tests.
+#[cfg(test)]
+pub(crate) struct Random(pub(crate) u64);
+
+#[cfg(test)]
+impl Random {
+    /// Next number below `n`.
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % n
+    }
+}
@@ -0,0 +1,23 @@
+use std::collections::BTreeSet;
+
+/// Rule found by [`RuleGenerator`](crate::RuleGenerator), with its statistics on the training
+/// data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    /// Label the pattern votes for.
+    pub label: String,
+    /// Binarized columns and the value each must take.
+    pub literals: BTreeSet<(bool, String)>,
+    /// Number of literals.
+    pub degree: usize,
+    /// Training rows of every label covered, in the order of
+    /// [`get_labels`](crate::RuleGenerator::get_labels). Rows count for their weight when the
+    /// model was fitted with weights.
+    pub coverage: Vec<f64>,
+    /// Share of the rows of `label` covered.
+    pub prevalence: f64,
+    /// Share of the covered rows having `label`.
+    pub homogeneity: f64,
+    /// Position in the order patterns were found.
+    pub index: usize,
+}
@@ -1,19 +1,277 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use super::binarize::Binarizer;
+use super::binarize::{row_weights, Binarizer};
+use super::bitset::{BitMatrix, Bitset};
+use super::max_coverage;
+use super::pattern::Pattern;
+use super::set_cover;
+use super::support_set::SelectionMode;
+use crate::error::{LadError, LadResult};
+use crate::persistence::{from_values, to_values, RuleGeneratorModel, RuleModel};
+use crate::progress::{CancellationToken, Event, Observer};
 use polars::prelude::*;
 use rayon::prelude::*;
-use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-type Pattern = HashSet<(bool, String)>;
+type Literals = BTreeSet<(bool, String)>;
+
+/// Number of candidate patterns whose coverage is computed in parallel before they are checked
+/// in order.
+const CHUNK_SIZE: usize = 256;
+
+/// How [`RuleGenerator::predict`] combines the rules covering a row into a label.
+///
+/// Rows no rule covers get the fallback label. When several labels tie for the best score, the
+/// fallback label wins if it is among them, otherwise the label seen first during `fit`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecisionMode {
+    /// Label of the first rule covering the row, in the order the rules were generated.
+    #[default]
+    FirstMatch,
+    /// Label with the most rules covering the row.
+    MajorityVote,
+    /// Label with the highest discriminant, the sum of the weights of its rules covering the row.
+    /// A rule is weighted by its prevalence, the share of its class it covers in the training
+    /// data, normalized so the weights of every class sum to one.
+    WeightedDiscriminant,
+}
+
+/// Why [`RuleGenerator::explain`] gave a row its label.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    pub label: String,
+    /// Whether no rule decided the label, either because none covered the row or because the
+    /// covering rules scored zero.
+    pub used_fallback: bool,
+    /// Rules covering the row, in the order they were generated.
+    pub rules: Vec<FiredRule>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiredRule {
+    /// [`Pattern::index`] of the rule.
+    pub index: usize,
+    /// Label the rule votes for.
+    pub label: String,
+    /// Literals of the rule, such as `age > 42.5`, `age <= 42.5` or `color != red`.
+    pub literals: Vec<String>,
+}
+
+/// Fewest rows of its label a pattern must cover, counted among the rows no earlier pattern
+/// covers. Rows are counted once whatever their weight. [`MinCoverage::Fraction`] scales with
+/// the size of every label, and [`RuleGenerator::with_label_min_coverage`] sets it per label.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MinCoverage {
+    Count(usize),
+    /// Share of the training rows of the label, between 0 and 1.
+    Fraction(f64),
+}
+
+impl Default for MinCoverage {
+    fn default() -> Self {
+        Self::Count(1)
+    }
+}
+
+impl MinCoverage {
+    /// Number of rows needed for a label with `size` training rows, at least one.
+    fn rows(self, size: usize) -> usize {
+        match self {
+            Self::Count(count) => count.max(1),
+            Self::Fraction(fraction) => ((fraction * size as f64).ceil() as usize).max(1),
+        }
+    }
+}
+
+/// Label predicted for rows no rule covers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FallbackPolicy {
+    /// Label with the most training rows no rule covers, or with the most training rows when
+    /// rules cover them all.
+    #[default]
+    MostRemaining,
+    /// Label with the most training rows. [`RuleGenerator::predict_proba`] gives uncovered rows
+    /// the share of every label in the training rows.
+    Priors,
+    /// The given label, which must be among [`RuleGenerator::get_labels`].
+    Fixed(String),
+}
+
+/// What [`RuleGenerator::prune`] removed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PruneReport {
+    /// Rules no longer in the model, in the order they were generated.
+    pub dropped: Vec<Pattern>,
+    /// Rows covered by a rule of their label that counts for them, see [`RuleGenerator::prune`],
+    /// before pruning and after alike.
+    pub covered_rows: usize,
+}
+
+/// Why [`RuleGenerator::fit`] stopped searching for patterns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// The strategy searched everything it was asked to.
+    Completed,
+    /// [`RuleGenerator::with_max_duration`] elapsed.
+    MaxDuration,
+    /// The next degree had more candidates than
+    /// [`RuleGenerator::with_max_candidates_per_degree`] allows.
+    MaxCandidates,
+    /// The token of [`RuleGenerator::with_cancellation_token`] was cancelled.
+    Cancelled,
+}
+
+/// What [`RuleGenerator::fit`] did. Rules found before stopping are kept whatever the reason,
+/// and a limit reached while making them spanned or strong leaves the rest as found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FitReport {
+    pub stop_reason: StopReason,
+    pub elapsed: Duration,
+    /// Training rows no rule of their label covers.
+    pub uncovered_rows: usize,
+}
+
+/// How [`RuleGenerator::fit`] searches for patterns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatternStrategy {
+    /// Extends short patterns one literal at a time, up to the maximum degree.
+    #[default]
+    BottomUp,
+    /// Shrinks the full description of every uncovered row while it stays homogeneous, which
+    /// reaches patterns of high degree.
+    TopDown,
+    /// Repeatedly finds the pattern covering the most uncovered rows of every label while staying
+    /// homogeneous on all rows of the other labels, as in the LAD integer program. The program
+    /// is solved by a combinatorial branch and bound over the literals rather than a MILP solver,
+    /// and is exponential in the worst case.
+    MaxCoverage,
+}
+
+/// Which patterns [`RuleGenerator::fit`] keeps once a strategy has found them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatternType {
+    /// Patterns as found, from which no literal can be dropped in the bottom-up strategy.
+    #[default]
+    Prime,
+    /// Patterns extended with every literal holding on all the training rows they cover, see
+    /// [`RuleGenerator::span`].
+    Spanned,
+    /// Patterns whose training rows of their label no other pattern of the label covers a
+    /// strict superset of. Among patterns covering the same rows the first one is kept. A pattern
+    /// is only dropped if [`DecisionMode::FirstMatch`] still predicts the training rows it covers
+    /// as with prime patterns.
+    Strong,
+}
+
+/// State shared by the pattern strategies during `fit`.
+struct Search {
+    data: BitMatrix,
+    features: Vec<String>,
+    /// Training rows of each label
+    classes: Vec<Bitset>,
+    /// Rows of each label not covered by any pattern yet
+    remaining: Vec<Bitset>,
+    /// Fewest uncovered rows of each label a pattern must cover
+    min_rows: Vec<usize>,
+    /// Weight of every row, `None` when they all weigh one
+    weights: Option<Vec<f64>>,
+    /// Patterns each label may still get
+    budgets: Vec<usize>,
+    patterns: Vec<Pattern>,
+    limits: Limits,
+    /// Why the search ended early, if it did
+    stop_reason: Option<StopReason>,
+}
+
+/// Bounds on how long `fit` may search.
+struct Limits {
+    deadline: Option<Instant>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl Limits {
+    /// Reason to stop now, if any.
+    fn reached(&self) -> Option<StopReason> {
+        if self
+            .cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            Some(StopReason::Cancelled)
+        } else if self.deadline.is_some_and(|x| Instant::now() >= x) {
+            Some(StopReason::MaxDuration)
+        } else {
+            None
+        }
+    }
+}
+
+impl Search {
+    /// Whether the search must stop, recording why the first time.
+    fn stopped(&mut self) -> bool {
+        if self.stop_reason.is_none() {
+            self.stop_reason = self.limits.reached();
+        }
+        self.stop_reason.is_some()
+    }
+
+    /// Whether a limit is reached now, recording it as the reason to stop if none was yet.
+    /// Unlike [`Self::stopped`], the search ending early for another reason does not count.
+    fn limit_reached(&mut self) -> bool {
+        let Some(reason) = self.limits.reached() else {
+            return false;
+        };
+        self.stop_reason.get_or_insert(reason);
+        true
+    }
+
+    /// Uncovered rows of every label in `covered`.
+    fn counts(&self, covered: &Bitset) -> Vec<usize> {
+        self.remaining
+            .iter()
+            .map(|x| covered.and_count(x))
+            .collect()
+    }
+
+    /// Weight of the uncovered rows of every label in `covered`, whose `counts` are known.
+    fn weighed(&self, covered: &Bitset, counts: &[usize]) -> Vec<f64> {
+        match &self.weights {
+            #[allow(clippy::cast_precision_loss)]
+            None => counts.iter().map(|&x| x as f64).collect(),
+            Some(weights) => self
+                .remaining
+                .iter()
+                .map(|x| covered.and_weight(x, Some(weights)))
+                .collect(),
+        }
+    }
+}
 
 pub struct RuleGenerator {
     bin: Binarizer,
     max: usize,
-    rules: Vec<(String, HashSet<(bool, String)>)>,
+    rules: Vec<Pattern>,
+    min_purity: f64,
+    min_coverage: MinCoverage,
+    label_min_coverage: BTreeMap<String, MinCoverage>,
+    max_patterns: BTreeMap<String, usize>,
+    strategy: PatternStrategy,
+    pattern_type: PatternType,
+    /// Training labels with their data type and name, in order of first appearance
+    classes: Series,
+    /// Text of every label in `classes`
     labels: Vec<String>,
+    /// Share of every label in the training rows
+    priors: Vec<f64>,
+    fallback: FallbackPolicy,
     fallback_label: Option<String>,
+    decision_mode: DecisionMode,
+    max_duration: Option<Duration>,
+    max_candidates_per_degree: Option<usize>,
+    cancellation_token: Option<CancellationToken>,
+    observer: Option<Arc<dyn Observer>>,
 }
 
 impl RuleGenerator {
@@ -22,297 +280,1599 @@ impl RuleGenerator {
             bin: bin.clone(),
             max,
             rules: Vec::new(),
+            min_purity: 1.0,
+            min_coverage: MinCoverage::Count(1),
+            label_min_coverage: BTreeMap::new(),
+            max_patterns: BTreeMap::new(),
+            strategy: PatternStrategy::BottomUp,
+            pattern_type: PatternType::Prime,
+            classes: Series::new_empty(PlSmallStr::EMPTY, &DataType::Null),
             labels: Vec::new(),
+            priors: Vec::new(),
+            fallback: FallbackPolicy::MostRemaining,
             fallback_label: None,
+            decision_mode: DecisionMode::FirstMatch,
+            max_duration: None,
+            max_candidates_per_degree: None,
+            cancellation_token: None,
+            observer: None,
         }
     }
 
-    pub fn get_rules(&self) -> Vec<(String, HashSet<(bool, String)>)> {
+    #[must_use]
+    pub const fn with_decision_mode(mut self, mode: DecisionMode) -> Self {
+        self.decision_mode = mode;
+        self
+    }
+
+    /// Accepts patterns whose covered rows are at least `min_purity` of one label, a value
+    /// between 0 and 1. Purity is measured on the rows no earlier pattern covers. The default
+    /// of 1 only accepts patterns covering a single label.
+    #[must_use]
+    pub const fn with_min_purity(mut self, min_purity: f64) -> Self {
+        self.min_purity = min_purity;
+        self
+    }
+
+    /// Discards patterns covering fewer rows of their label than `min_coverage`, along with
+    /// candidates that cannot reach it for any label.
+    #[must_use]
+    pub const fn with_min_coverage(mut self, min_coverage: MinCoverage) -> Self {
+        self.min_coverage = min_coverage;
+        self
+    }
+
+    /// Replaces the minimum coverage for patterns of `label` only, so that a rare label can ask
+    /// for fewer rows than the others. Labels are given as in [`get_labels`](Self::get_labels).
+    #[must_use]
+    pub fn with_label_min_coverage(
+        mut self,
+        label: impl Into<String>,
+        min_coverage: MinCoverage,
+    ) -> Self {
+        self.label_min_coverage.insert(label.into(), min_coverage);
+        self
+    }
+
+    /// Stops accepting patterns for `label` once it has `max_patterns` of them, leaving its
+    /// remaining rows to the fallback label and the other labels' candidates to the search.
+    #[must_use]
+    pub fn with_max_patterns(mut self, label: impl Into<String>, max_patterns: usize) -> Self {
+        self.max_patterns.insert(label.into(), max_patterns);
+        self
+    }
+
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: FallbackPolicy) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_strategy(mut self, strategy: PatternStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_pattern_type(mut self, pattern_type: PatternType) -> Self {
+        self.pattern_type = pattern_type;
+        self
+    }
+
+    /// Stops `fit` once it has searched for `max_duration`. The time is checked between small
+    /// steps of the search, such as a chunk of candidates or a row of the top-down strategy, so
+    /// `fit` overruns it by about one step.
+    #[must_use]
+    pub const fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Stops the bottom-up strategy before a degree with more than `max_candidates` candidate
+    /// patterns. Other strategies do not enumerate degrees and ignore it.
+    #[must_use]
+    pub const fn with_max_candidates_per_degree(mut self, max_candidates: usize) -> Self {
+        self.max_candidates_per_degree = Some(max_candidates);
+        self
+    }
+
+    /// Stops `fit` once `token` is cancelled, checked as often as the maximum duration.
+    #[must_use]
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn get_rules(&self) -> Vec<Pattern> {
         self.rules.clone()
     }
 
-    pub fn predict(&self, data: &DataFrame) -> PolarsResult<Vec<String>> {
-        let data: DataFrame = self.bin.transform(data).unwrap();
-        let mut predictions: Vec<Option<String>> = vec![None; data.height()];
+    /// Text of the labels seen during `fit`, in order of first appearance. Strings are shown
+    /// without quotes. Patterns, explanations and score columns name labels this way.
+    pub fn get_labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
 
-        for (label, pattern) in &self.rules {
-            let coverage = self.coverage(&data, pattern);
+    /// Labels seen during `fit`, in order of first appearance, with the data type and name of
+    /// the training labels.
+    pub fn get_classes(&self) -> Series {
+        self.classes.clone()
+    }
 
-            // Iterate over each index in the coverage vector (a)
-            for (&is_covered, prediction) in coverage.iter().zip(predictions.iter_mut()) {
-                // If the current value is true and the result is None for this index, set the label
-                if is_covered && prediction.is_none() {
-                    *prediction = Some(label.clone()); // Use .clone() since label is a reference
-                }
+    /// Label of every row, with the data type and name of the training labels. Rows the
+    /// binarizer drops for their missing values get the fallback label.
+    pub fn predict(&self, data: &DataFrame) -> LadResult<Series> {
+        let Some(fallback_label) = &self.fallback_label else {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        };
+        let fallback = self.label_index(fallback_label)? as IdxSize;
+        let data = BitMatrix::new(&self.bin.transform_all(data)?)?;
+        let indices = self
+            .decisions(&data)?
+            .into_iter()
+            .map(|x| x.map_or(fallback, |i| i as IdxSize))
+            .collect();
+        Ok(self
+            .classes
+            .take(&IdxCa::from_vec(self.classes.name().clone(), indices))?)
+    }
+
+    /// Predicts like [`predict`](Self::predict) and lists the rules covering every row.
+    pub fn explain(&self, data: &DataFrame) -> LadResult<Vec<Explanation>> {
+        let Some(fallback_label) = &self.fallback_label else {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        };
+        let data = BitMatrix::new(&self.bin.transform_all(data)?)?;
+        let mut explanations = self
+            .decisions(&data)?
+            .into_iter()
+            .map(|x| Explanation {
+                label: x.map_or_else(|| fallback_label.clone(), |i| self.labels[i].clone()),
+                used_fallback: x.is_none(),
+                rules: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        for pattern in &self.rules {
+            let literals = pattern
+                .literals
+                .iter()
+                .map(|(value, column)| describe(*value, column))
+                .collect::<Vec<_>>();
+            for row in data.coverage(&pattern.literals)?.iter_ones() {
+                explanations[row].rules.push(FiredRule {
+                    index: pattern.index,
+                    label: pattern.label.clone(),
+                    literals: literals.clone(),
+                });
             }
         }
-        Ok(predictions
-            .iter()
-            .map(|x| {
-                x.as_ref().map_or_else(
-                    || self.fallback_label.clone().expect("fallback not found"),
-                    std::clone::Clone::clone,
-                )
-            })
-            .collect())
+        Ok(explanations)
     }
 
-    pub fn fit(&mut self, data: &DataFrame, labels: &Series) -> PolarsResult<()> {
-        //println!("Debug0");
-        let features = data.get_column_names();
-        // Ensure y is categorical or can be grouped
-        let unique_y = labels.unique_stable()?;
-        self.labels = unique_y.iter().map(|x| x.to_string()).collect();
-        // Initialize a Vec to hold the resulting DataFrames
-        let grouped_dfs: Vec<DataFrame> = self.divide_data(data, labels);
-        //println!("Debug2");
+    /// Replaces every rule by its spanned pattern on the binarized training `data`: all the
+    /// literals holding on every row the rule covers. Spanned patterns cover the same training
+    /// rows but ask more of unseen rows.
+    pub fn span(&mut self, data: &DataFrame) -> LadResult<()> {
+        if self.fallback_label.is_none() {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        }
+        let features = data
+            .get_column_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let data = BitMatrix::new(data)?;
+        for pattern in &mut self.rules {
+            pattern.literals = spanned(&data, &features, &pattern.literals)?;
+            pattern.degree = pattern.literals.len();
+        }
+        Ok(())
+    }
 
-        self.fallback_label = grouped_dfs
+    /// Drops rules until every row of `data` covered by a rule of its label is covered by as
+    /// few rules as `mode` finds, keeping the remaining rules in order.
+    ///
+    /// Under [`DecisionMode::FirstMatch`] a rule only counts for a row if no rule of another
+    /// label comes before it on that row, so rows predicted right keep their prediction.
+    ///
+    /// Like [`fit`](Self::fit), `data` is already binarized, and `labels` may only hold
+    /// training labels.
+    pub fn prune(
+        &mut self,
+        data: &DataFrame,
+        labels: &Series,
+        mode: SelectionMode,
+    ) -> LadResult<PruneReport> {
+        if self.fallback_label.is_none() {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        }
+        if data.height() != labels.len() {
+            return Err(LadError::LengthMismatch {
+                data: data.height(),
+                label: labels.len(),
+            });
+        }
+        if labels.null_count() > 0 {
+            return Err(LadError::MissingLabels(labels.null_count()));
+        }
+        if let Some(label) = labels
             .iter()
+            .map(|x| label_text(&x))
+            .find(|x| !self.labels.contains(x))
+        {
+            return Err(LadError::UnknownLabel(label));
+        }
+        let data = BitMatrix::new(data)?;
+        let classes = self.rows_of(labels);
+
+        // Rules of its label covering each row, and whether a rule of another label came first
+        let mut covering = vec![Vec::new(); data.height()];
+        let mut preceded = vec![false; data.height()];
+        let first_match = self.decision_mode == DecisionMode::FirstMatch;
+        for (k, pattern) in self.rules.iter().enumerate() {
+            let own = &classes[self.label_index(&pattern.label)?];
+            for row in data.coverage(&pattern.literals)?.iter_ones() {
+                if !own.contains(row) {
+                    preceded[row] = true;
+                } else if !(first_match && preceded[row]) {
+                    covering[row].push(k);
+                }
+            }
+        }
+        let mut elements = covering
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>();
+        let covered_rows = elements.len();
+        elements.sort_unstable();
+        elements.dedup();
+
+        let selected = match mode {
+            SelectionMode::Greedy => set_cover::greedy(&elements, self.rules.len()),
+            SelectionMode::Exact => set_cover::exact(&elements, self.rules.len()),
+        };
+        let (kept, dropped) = std::mem::take(&mut self.rules)
+            .into_iter()
             .enumerate()
-            .max_by(|(_, a), (_, b)| a.shape().0.cmp(&b.shape().0))
-            .map(|(i, _)| self.labels[i].clone());
+            .partition::<Vec<_>, _>(|(k, _)| selected.binary_search(k).is_ok());
+        self.rules = kept.into_iter().map(|(_, x)| x).collect();
+        Ok(PruneReport {
+            dropped: dropped.into_iter().map(|(_, x)| x).collect(),
+            covered_rows,
+        })
+    }
+
+    /// Score of every label for each row, one `f64` column per label in the order of
+    /// [`get_labels`](Self::get_labels).
+    ///
+    /// Under [`DecisionMode::FirstMatch`] the label of the first rule covering the row scores one
+    /// and the others zero. Otherwise scores are the number of rules of the label covering the
+    /// row, or its weighted discriminant under [`DecisionMode::WeightedDiscriminant`]. Rows no
+    /// rule covers score zero everywhere.
+    pub fn decision_function(&self, data: &DataFrame) -> LadResult<DataFrame> {
+        if self.fallback_label.is_none() {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        }
+        let data = BitMatrix::new(&self.bin.transform_all(data)?)?;
+        self.to_frame(&self.scores(&data)?)
+    }
+
+    /// Scores of [`decision_function`](Self::decision_function) normalized to sum to one on
+    /// every row. Rows no rule covers put all their mass on the fallback label, or get the
+    /// training share of every label under [`FallbackPolicy::Priors`].
+    pub fn predict_proba(&self, data: &DataFrame) -> LadResult<DataFrame> {
+        let Some(fallback_label) = &self.fallback_label else {
+            return Err(LadError::NotFitted("RuleGenerator"));
+        };
+        let fallback = self.label_index(fallback_label)?;
+        let data = BitMatrix::new(&self.bin.transform_all(data)?)?;
+        let mut scores = self.scores(&data)?;
+        for row in &mut scores {
+            let total = row.iter().sum::<f64>();
+            if total > 0.0 {
+                row.iter_mut().for_each(|x| *x /= total);
+            } else if self.fallback == FallbackPolicy::Priors && !self.priors.is_empty() {
+                row.clone_from(&self.priors);
+            } else {
+                row[fallback] = 1.0;
+            }
+        }
+        self.to_frame(&scores)
+    }
+
+    /// Generates patterns degree by degree. Candidates are checked in a fixed order, so the
+    /// same data always gives the same rules in the same order.
+    ///
+    /// With `weights`, rows count for their weight in the purity and statistics of patterns and
+    /// in the choice of the fallback label.
+    ///
+    /// Reaching a limit ends the search early without an error: the rules found so far are
+    /// kept and the report says which limit stopped it.
+    pub fn fit(
+        &mut self,
+        data: &DataFrame,
+        labels: &Series,
+        weights: Option<&Series>,
+    ) -> LadResult<FitReport> {
+        let start = Instant::now();
+        if data.height() != labels.len() {
+            return Err(LadError::LengthMismatch {
+                data: data.height(),
+                label: labels.len(),
+            });
+        }
+        if labels.null_count() > 0 {
+            return Err(LadError::MissingLabels(labels.null_count()));
+        }
+        if !(0.0..=1.0).contains(&self.min_purity) {
+            return Err(LadError::InvalidParameter(format!(
+                "minimum purity {} outside [0, 1]",
+                self.min_purity
+            )));
+        }
+        for min_coverage in
+            std::iter::once(&self.min_coverage).chain(self.label_min_coverage.values())
+        {
+            if let MinCoverage::Fraction(fraction) = min_coverage {
+                if !(0.0..=1.0).contains(fraction) {
+                    return Err(LadError::InvalidParameter(format!(
+                        "minimum coverage {fraction} outside [0, 1]"
+                    )));
+                }
+            }
+        }
+        let weights = row_weights(weights, data.height())?;
+        let features = data
+            .get_column_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        self.classes = labels.unique_stable()?;
+        self.labels = self.classes.iter().map(|x| label_text(&x)).collect();
+        let fixed = match &self.fallback {
+            FallbackPolicy::Fixed(label) => Some(label),
+            _ => None,
+        };
+        let configured = self
+            .label_min_coverage
+            .keys()
+            .chain(self.max_patterns.keys());
+        if let Some(label) = fixed
+            .into_iter()
+            .chain(configured)
+            .find(|x| !self.labels.contains(x))
+        {
+            return Err(LadError::UnknownLabel(label.clone()));
+        }
+        let classes = self.divide_data(labels)?;
+        let sizes = classes
+            .iter()
+            .map(|x| x.weight(weights.as_deref()))
+            .collect::<Vec<_>>();
+        let total = sizes.iter().sum::<f64>();
+        self.priors = sizes
+            .iter()
+            .map(|x| if total > 0.0 { x / total } else { 0.0 })
+            .collect();
+        let mut search = Search {
+            data: BitMatrix::new(data)?,
+            features,
+            min_rows: classes
+                .iter()
+                .zip(&self.labels)
+                .map(|(x, label)| {
+                    self.label_min_coverage
+                        .get(label)
+                        .unwrap_or(&self.min_coverage)
+                        .rows(x.count())
+                })
+                .collect(),
+            remaining: classes.clone(),
+            classes,
+            weights,
+            budgets: self
+                .labels
+                .iter()
+                .map(|x| self.max_patterns.get(x).copied().unwrap_or(usize::MAX))
+                .collect(),
+            patterns: Vec::new(),
+            limits: Limits {
+                deadline: self.max_duration.map(|x| start + x),
+                cancellation_token: self.cancellation_token.clone(),
+            },
+            stop_reason: None,
+        };
+
+        match self.strategy {
+            PatternStrategy::BottomUp => self.bottom_up(&mut search)?,
+            PatternStrategy::TopDown => self.top_down(&mut search)?,
+            PatternStrategy::MaxCoverage => self.max_coverage(&mut search)?,
+        }
+
+        // Rows left uncovered are the ones the fallback label applies to
+        let weights = search.weights.as_deref();
+        self.fallback_label = match &self.fallback {
+            FallbackPolicy::MostRemaining if search.remaining.iter().any(|x| x.count() > 0) => {
+                self.largest(&search.remaining, weights)
+            }
+            FallbackPolicy::MostRemaining | FallbackPolicy::Priors => {
+                self.largest(&search.classes, weights)
+            }
+            FallbackPolicy::Fixed(label) => Some(label.clone()),
+        };
+
+        match self.pattern_type {
+            PatternType::Prime => {}
+            PatternType::Spanned => {
+                // Patterns left once a limit is reached stay prime
+                for k in 0..search.patterns.len() {
+                    if search.limit_reached() {
+                        break;
+                    }
+                    let literals =
+                        spanned(&search.data, &search.features, &search.patterns[k].literals)?;
+                    search.patterns[k].degree = literals.len();
+                    search.patterns[k].literals = literals;
+                }
+            }
+            PatternType::Strong => {
+                let fallback = self
+                    .fallback_label
+                    .as_deref()
+                    .map_or(Ok(0), |x| self.label_index(x))?;
+                search.patterns = self.strong(&mut search, fallback)?;
+            }
+        }
+
+        self.rules = search.patterns;
+
+        Ok(FitReport {
+            stop_reason: search.stop_reason.unwrap_or(StopReason::Completed),
+            elapsed: start.elapsed(),
+            uncovered_rows: search.remaining.iter().map(Bitset::count).sum(),
+        })
+    }
 
-        // Perform further operations with grouped_dfs if necessary
-        let prime_patterns: Vec<(String, Pattern)> = Vec::new();
-        let mut prev_degree_patterns: Vec<Pattern> = vec![HashSet::new()];
+    /// Enumerates patterns by increasing degree, extending the candidates of every degree that
+    /// cover rows of several labels.
+    fn bottom_up(&mut self, search: &mut Search) -> LadResult<()> {
+        let mut prev_degree_patterns: Vec<Literals> = vec![BTreeSet::new()];
 
-        if self.max > features.len() || self.max == 0 {
-            self.max = features.len();
+        if self.max > search.features.len() || self.max == 0 {
+            self.max = search.features.len();
         }
-        let grouped_dfs = Arc::new(Mutex::new(grouped_dfs));
-        let prime_patterns = Arc::new(Mutex::new(prime_patterns));
 
         for d in 1..=self.max {
-            println!("{d}");
+            self.emit(&Event::DegreeStarted { degree: d });
 
             let start_time = Instant::now();
 
-            let curr_degree_patterns = Arc::new(Mutex::new(Vec::new()));
-
-            prev_degree_patterns
-                .par_iter()
-                .cloned()
-                .for_each(|curr_pattern| {
-                    features.par_iter().for_each(|feature| {
-                        [true, false].par_iter().for_each(|&term| {
-                            let mut next_pattern = curr_pattern.clone();
-                            let should_break = !next_pattern.insert((term, feature.to_string()));
-                            if should_break {
-                                return;
-                            }
-
-                            // Check if subpatterns exist in prev_degree_patterns
-                            let any_break = next_pattern.iter().any(|t| {
-                                let mut test_pattern = next_pattern.clone();
-                                test_pattern.remove(t);
-                                !prev_degree_patterns.contains(&test_pattern)
-                            });
-
-                            if any_break {
-                                return;
-                            }
-
-                            // Lock for access to grouped_dfs
-                            let shapes: Vec<_> = grouped_dfs
-                                .lock()
-                                .unwrap()
-                                .iter()
-                                .map(DataFrame::shape)
-                                .collect();
-
-                            let counts: Vec<_> = grouped_dfs
-                                .lock()
-                                .unwrap()
-                                .iter()
-                                .map(|df| {
-                                    df.filter(
-                                        &self.coverage(df, &next_pattern).into_iter().collect(),
-                                    )
-                                    .unwrap()
-                                    .shape()
-                                    .0
-                                })
-                                .collect();
-
-                            let tmp = counts
-                                .par_iter()
-                                .map(|&x| usize::from(x >= 1))
-                                .sum::<usize>();
-
-                            if tmp == 1 {
-                                for (i, count) in counts.iter().enumerate() {
-                                    if *count == 0 || shapes[i].0 == 0 {
-                                        continue;
-                                    }
-
-                                    let mut grouped_dfs_locked = grouped_dfs.lock().unwrap();
-
-                                    grouped_dfs_locked[i] = grouped_dfs_locked[i]
-                                        .filter(
-                                            &self
-                                                .coverage(&grouped_dfs_locked[i], &next_pattern)
-                                                .into_iter()
-                                                .map(|x| !x)
-                                                .collect(),
-                                        )
-                                        .unwrap();
-                                    drop(grouped_dfs_locked);
-
-                                    prime_patterns
-                                        .lock()
-                                        .unwrap()
-                                        .push((self.labels[i].clone(), next_pattern.clone()));
-
-                                    break;
-                                }
-                            } else if tmp == 0 {
-                                return;
-                            } else {
-                                curr_degree_patterns.lock().unwrap().push(next_pattern);
-                            }
-                        });
-                    });
-                });
+            let mut curr_degree_patterns = Vec::new();
+            let max_candidates = self.max_candidates_per_degree.unwrap_or(usize::MAX);
+            let mut candidates = Self::candidates(
+                &prev_degree_patterns,
+                &search.features,
+                &search.limits,
+                max_candidates,
+            );
+            // A degree stopped before its first candidate still finishes for observers
+            if candidates.len() > max_candidates {
+                search.stop_reason = Some(StopReason::MaxCandidates);
+                candidates.clear();
+            } else if search.stopped() {
+                candidates.clear();
+            }
 
-            //for curr_pattern in prev_degree_patterns.clone() {
-            //    for feature in features.clone() {
-            //        for term in [true, false] {
-            //            let mut next_pattern = curr_pattern.clone();
-            //            let mut should_break = !next_pattern.insert((term, feature.to_string()));
-            //            if should_break {
-            //                continue;
-            //            }
-            //            for t in next_pattern.clone() {
-            //                let mut test_pattern = next_pattern.clone();
-            //                test_pattern.remove(&t);
-            //                if !prev_degree_patterns.contains(&test_pattern) {
-            //                    should_break = true;
-            //                    break;
-            //                }
-            //            }
-            //            if should_break {
-            //                continue;
-            //            }
-            //            let shapes = grouped_dfs.iter().map(DataFrame::shape).collect::<Vec<_>>();
-            //            //println!("{shapes:?}");
-            //            let counts: Vec<_> = grouped_dfs
-            //                .iter()
-            //                .map(|x| {
-            //                    x.filter(&self.coverage(x, &next_pattern).into_iter().collect())
-            //                        .unwrap()
-            //                        .shape()
-            //                        .0
-            //                })
-            //                .collect();
-            //            let tmp = counts.iter().map(|x| usize::from(*x >= 1)).sum::<usize>();
-            //
-            //            //println!("0: {counts:?} {tmp}");
-            //            if tmp == 1 {
-            //                //println!("1: {counts:?} {tmp}");
-            //                for i in 0..counts.len() {
-            //                    if counts[i] == 0 || shapes[i].0 == 0 {
-            //                        continue;
-            //                    }
-            //                    //println!("{i}");
-            //                    grouped_dfs[i] = grouped_dfs[i]
-            //                        .filter(
-            //                            &self
-            //                                .coverage(&grouped_dfs[i], &next_pattern)
-            //                                .into_iter()
-            //                                .map(|x| !x)
-            //                                .collect(),
-            //                        )
-            //                        .unwrap();
-            //
-            //                    prime_patterns.push((self.labels[i].clone(), next_pattern));
-            //                    break;
-            //                }
-            //            } else if tmp == 0 {
-            //                continue;
-            //            } else {
-            //                curr_degree_patterns.push(next_pattern);
-            //            }
-            //        }
-            //    }
-            //}
-
-            let duration = start_time.elapsed();
-            println!("Time taken: {:.2} milliseconds", duration.as_millis());
-
-            let shapes = grouped_dfs
-                .lock()
-                .unwrap()
-                .iter()
-                .map(|x| x.shape().0)
-                .collect::<Vec<_>>();
-            println!("{shapes:?}");
+            for chunk in candidates.chunks(CHUNK_SIZE) {
+                if search.stopped() {
+                    break;
+                }
+                let coverages = chunk
+                    .par_iter()
+                    .map(|pattern| search.data.coverage(pattern))
+                    .collect::<LadResult<Vec<_>>>()?;
 
-            if shapes.iter().sum::<usize>() == 0 {
-                break;
+                for (next_pattern, covered) in chunk.iter().zip(coverages) {
+                    let counts = search.counts(&covered);
+                    let weighed = search.weighed(&covered, &counts);
+
+                    let tmp = counts.iter().filter(|&&x| x >= 1).count();
+                    // Label covering the most weight, the first one on ties
+                    let i = weighed
+                        .iter()
+                        .enumerate()
+                        .max_by(|(i, a), (j, b)| a.total_cmp(b).then(j.cmp(i)))
+                        .map_or(0, |(i, _)| i);
+
+                    // Extensions cover fewer rows, so candidates too small for every label
+                    // that may still get patterns are dropped
+                    let supported = (0..counts.len())
+                        .any(|k| search.budgets[k] > 0 && counts[k] >= search.min_rows[k]);
+
+                    // Homogeneous candidates of labels out of budget are extended like the
+                    // others, as their extensions may suit another label
+                    if counts[i] >= search.min_rows[i]
+                        && self.homogeneous(&weighed, i)
+                        && search.budgets[i] > 0
+                    {
+                        self.accept(search, i, next_pattern.clone(), &covered);
+                    } else if tmp > 1 && supported {
+                        curr_degree_patterns.push(next_pattern.clone());
+                    }
+                }
             }
-            if d == self.max {
-                self.fallback_label = grouped_dfs
-                    .lock()
-                    .unwrap()
-                    .iter()
-                    .enumerate()
-                    .max_by(|(_, a), (_, b)| a.shape().0.cmp(&b.shape().0))
-                    .map(|(i, _)| self.labels[i].clone());
+
+            self.emit(&Event::DegreeFinished {
+                degree: d,
+                elapsed: start_time.elapsed(),
+                candidates: curr_degree_patterns.len(),
+            });
+
+            if self.emit_remaining(search) == 0 || search.stop_reason.is_some() {
+                break;
             }
-            prev_degree_patterns = Arc::try_unwrap(curr_degree_patterns)
-                .unwrap()
-                .into_inner()
-                .unwrap();
+            prev_degree_patterns = curr_degree_patterns;
         }
+        Ok(())
+    }
 
-        self.rules.clone_from(&prime_patterns.lock().unwrap());
+    /// Starts from the characteristic term of every uncovered row, which fixes every column to
+    /// the row's value, and drops literals in order as long as the pattern stays homogeneous.
+    /// Patterns can have any degree, `max` does not apply.
+    fn top_down(&self, search: &mut Search) -> LadResult<()> {
+        for i in 0..self.labels.len() {
+            let rows = search.classes[i].iter_ones().collect::<Vec<_>>();
+            for row in rows {
+                if search.budgets[i] == 0 || search.stopped() {
+                    break;
+                }
+                if !search.remaining[i].contains(row) {
+                    continue;
+                }
+                let mut literals = Literals::new();
+                for feature in &search.features {
+                    if let Some(value) = search.data.value(feature, row)? {
+                        literals.insert((value, feature.clone()));
+                    }
+                }
+                let mut covered = search.data.coverage(&literals)?;
+                if literals.is_empty()
+                    || !self.homogeneous(&search.weighed(&covered, &search.counts(&covered)), i)
+                {
+                    continue;
+                }
+                for literal in literals.clone() {
+                    if literals.len() == 1 {
+                        break;
+                    }
+                    literals.remove(&literal);
+                    let wider = search.data.coverage(&literals)?;
+                    if self.homogeneous(&search.weighed(&wider, &search.counts(&wider)), i) {
+                        covered = wider;
+                    } else {
+                        literals.insert(literal);
+                    }
+                }
+                if search.counts(&covered)[i] >= search.min_rows[i] {
+                    self.accept(search, i, literals, &covered);
+                }
+            }
+        }
+        self.emit_remaining(search);
+        Ok(())
+    }
 
+    /// Takes the pattern covering the most uncovered rows of every label in turn until none
+    /// covers enough of them. Unlike the other strategies, purity counts the rows of other labels
+    /// that earlier patterns cover.
+    fn max_coverage(&self, search: &mut Search) -> LadResult<()> {
+        let max_degree = if self.max == 0 {
+            search.features.len()
+        } else {
+            self.max
+        };
+        for i in 0..self.labels.len() {
+            let mut negative = Bitset::zeros(search.data.height());
+            for (_, rows) in search.classes.iter().enumerate().filter(|(k, _)| *k != i) {
+                negative.or_assign(rows);
+            }
+            while search.budgets[i] > 0 && !search.stopped() {
+                let Some((literals, covered)) = max_coverage::solve(
+                    &search.data,
+                    &search.features,
+                    &search.remaining[i],
+                    &negative,
+                    search.weights.as_deref(),
+                    max_degree,
+                    search.min_rows[i],
+                    self.min_purity,
+                    &|| search.limits.reached().is_some(),
+                )?
+                else {
+                    // No pattern is left, unless the limits cut the search short
+                    search.stopped();
+                    break;
+                };
+                self.accept(search, i, literals, &covered);
+            }
+        }
+        self.emit_remaining(search);
         Ok(())
     }
+
+    /// Patterns of `search` not subsumed by another pattern of their label. Patterns are only
+    /// pure on the rows no earlier pattern covers, so a subsumed pattern is kept when dropping it
+    /// would change the first match label of a training row it covers, `fallback` being the label
+    /// of rows no pattern covers. Patterns left once a limit is reached are all kept.
+    fn strong(&self, search: &mut Search, fallback: usize) -> LadResult<Vec<Pattern>> {
+        let labels = search
+            .patterns
+            .iter()
+            .map(|x| self.label_index(&x.label))
+            .collect::<LadResult<Vec<_>>>()?;
+        let covered = search
+            .patterns
+            .iter()
+            .map(|x| search.data.coverage(&x.literals))
+            .collect::<LadResult<Vec<_>>>()?;
+        let own = covered
+            .iter()
+            .zip(&labels)
+            .map(|(x, &i)| {
+                let mut own = x.clone();
+                own.and_assign(&search.classes[i]);
+                own
+            })
+            .collect::<Vec<_>>();
+        let mut kept = vec![true; covered.len()];
+        for k in 0..covered.len() {
+            if search.limit_reached() {
+                break;
+            }
+            let subsumed = (0..covered.len()).any(|j| {
+                j != k
+                    && kept[j]
+                    && labels[j] == labels[k]
+                    && own[k].and_count(&own[j]) == own[k].count()
+                    && (own[j].count() > own[k].count() || j < k)
+            });
+            let first_match = |row: usize, skip: Option<usize>| {
+                (0..covered.len())
+                    .find(|&m| kept[m] && Some(m) != skip && covered[m].contains(row))
+                    .map_or(fallback, |m| labels[m])
+            };
+            if subsumed
+                && covered[k]
+                    .iter_ones()
+                    .all(|row| first_match(row, None) == first_match(row, Some(k)))
+            {
+                kept[k] = false;
+            }
+        }
+        Ok(search
+            .patterns
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(x, _)| x.clone())
+            .collect())
+    }
+
+    /// Whether the weight of uncovered rows of every label, `weighed`, is pure enough to make a
+    /// pattern for label `i`.
+    fn homogeneous(&self, weighed: &[f64], i: usize) -> bool {
+        let total = weighed.iter().sum::<f64>();
+        weighed[i] > 0.0 && (weighed[i] == total || weighed[i] / total >= self.min_purity)
+    }
+
+    /// Adds the pattern for label `i` covering `covered` to the rules.
+    fn accept(&self, search: &mut Search, i: usize, literals: Literals, covered: &Bitset) {
+        search.remaining[i].and_not_assign(covered);
+        search.budgets[i] -= 1;
+        let pattern = self.pattern(
+            i,
+            literals,
+            covered,
+            &search.classes,
+            search.weights.as_deref(),
+            search.patterns.len(),
+        );
+        self.emit(&Event::PatternAccepted { pattern: &pattern });
+        search.patterns.push(pattern);
+    }
+
+    /// Emits the number of rows of every label still uncovered and returns their total.
+    fn emit_remaining(&self, search: &Search) -> usize {
+        let counts = search
+            .remaining
+            .iter()
+            .map(Bitset::count)
+            .collect::<Vec<_>>();
+        self.emit(&Event::Remaining {
+            labels: &self.labels,
+            counts: &counts,
+        });
+        counts.iter().sum()
+    }
+
+    /// Pattern voting for label `i` with its statistics on the training rows `classes`.
+    fn pattern(
+        &self,
+        i: usize,
+        literals: Literals,
+        covered: &Bitset,
+        classes: &[Bitset],
+        weights: Option<&[f64]>,
+        index: usize,
+    ) -> Pattern {
+        let coverage = classes
+            .iter()
+            .map(|x| covered.and_weight(x, weights))
+            .collect::<Vec<_>>();
+        let total = coverage.iter().sum::<f64>();
+        let size = classes[i].weight(weights);
+        Pattern {
+            label: self.labels[i].clone(),
+            degree: literals.len(),
+            literals,
+            prevalence: if size > 0.0 { coverage[i] / size } else { 0.0 },
+            homogeneity: if total > 0.0 {
+                coverage[i] / total
+            } else {
+                0.0
+            },
+            coverage,
+            index,
+        }
+    }
+
+    /// Extensions of `patterns` by one literal, in order of the pattern they extend and then of
+    /// the feature added. Every pattern is only extended by features after its last one, and only
+    /// kept if all its subpatterns are among `patterns`.
+    ///
+    /// Enumeration ends early, with the candidates found so far, once `limits` are reached or
+    /// more than `max` candidates are found.
+    fn candidates(
+        patterns: &[Literals],
+        features: &[String],
+        limits: &Limits,
+        max: usize,
+    ) -> Vec<Literals> {
+        let index = features
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (x.as_str(), i))
+            .collect::<HashMap<_, _>>();
+        let known = patterns.iter().collect::<HashSet<_>>();
+        let mut candidates = Vec::new();
+        for pattern in patterns {
+            if candidates.len() > max || limits.reached().is_some() {
+                break;
+            }
+            let first = pattern
+                .iter()
+                .map(|(_, c)| index[c.as_str()] + 1)
+                .max()
+                .unwrap_or(0);
+            for feature in &features[first..] {
+                for term in [true, false] {
+                    let mut next_pattern = pattern.clone();
+                    next_pattern.insert((term, feature.clone()));
+
+                    // Check if subpatterns exist in the previous degree
+                    let all_known = next_pattern.iter().all(|t| {
+                        let mut test_pattern = next_pattern.clone();
+                        test_pattern.remove(t);
+                        known.contains(&test_pattern)
+                    });
+                    if all_known {
+                        candidates.push(next_pattern);
+                    }
+                }
+            }
+        }
+        candidates
+    }
 }
 
 impl RuleGenerator {
-    fn coverage(&self, data: &DataFrame, pattern: &Pattern) -> Vec<bool> {
-        let a = pattern
-            .iter()
-            .map(|(v, c)| {
-                data.column(c)
-                    .unwrap()
+    /// Index of the predicted label of each row, `None` where the fallback label applies.
+    fn decisions(&self, data: &BitMatrix) -> LadResult<Vec<Option<usize>>> {
+        Ok(match self.decision_mode {
+            DecisionMode::FirstMatch => self.first_match(data)?,
+            DecisionMode::MajorityVote | DecisionMode::WeightedDiscriminant => {
+                let fallback = self
+                    .fallback_label
+                    .as_deref()
+                    .map(|x| self.label_index(x))
+                    .transpose()?;
+                self.scores(data)?
                     .iter()
-                    .map(|val| {
-                        //println!("{val} = {v}");
-                        val.cast(&DataType::Boolean).eq(&AnyValue::Boolean(*v))
-                    })
-                    .collect::<Vec<bool>>()
+                    .map(|x| Self::decide(x, fallback))
+                    .collect()
+            }
+        })
+    }
+
+    /// Index of the label of the first rule covering each row.
+    fn first_match(&self, data: &BitMatrix) -> LadResult<Vec<Option<usize>>> {
+        let mut predictions = vec![None; data.height()];
+        for pattern in &self.rules {
+            let i = self.label_index(&pattern.label)?;
+            for row in data.coverage(&pattern.literals)?.iter_ones() {
+                predictions[row].get_or_insert(i);
+            }
+        }
+        Ok(predictions)
+    }
+
+    /// Score of every label for each row under the decision mode, zero for labels without a
+    /// rule covering the row. First match gives the whole score to the label of the first rule.
+    fn scores(&self, data: &BitMatrix) -> LadResult<Vec<Vec<f64>>> {
+        if self.decision_mode == DecisionMode::FirstMatch {
+            return Ok(self
+                .first_match(data)?
+                .into_iter()
+                .map(|x| {
+                    let mut scores = vec![0.0; self.labels.len()];
+                    if let Some(i) = x {
+                        scores[i] = 1.0;
+                    }
+                    scores
+                })
+                .collect());
+        }
+        let labels = self
+            .rules
+            .iter()
+            .map(|x| self.label_index(&x.label))
+            .collect::<LadResult<Vec<_>>>()?;
+        let mut totals = vec![0.0; self.labels.len()];
+        for (pattern, &i) in self.rules.iter().zip(&labels) {
+            totals[i] += pattern.prevalence;
+        }
+        let mut scores = vec![vec![0.0; self.labels.len()]; data.height()];
+        for (pattern, &i) in self.rules.iter().zip(&labels) {
+            let weight = match self.decision_mode {
+                DecisionMode::WeightedDiscriminant if totals[i] > 0.0 => {
+                    pattern.prevalence / totals[i]
+                }
+                DecisionMode::WeightedDiscriminant => 0.0,
+                _ => 1.0,
+            };
+            for row in data.coverage(&pattern.literals)?.iter_ones() {
+                scores[row][i] += weight;
+            }
+        }
+        Ok(scores)
+    }
+
+    fn to_frame(&self, scores: &[Vec<f64>]) -> LadResult<DataFrame> {
+        let columns = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                Series::new(
+                    label.as_str().into(),
+                    scores.iter().map(|x| x[i]).collect::<Vec<_>>(),
+                )
             })
-            .collect::<Vec<_>>();
-        let inner_length = a[0].len(); // Assuming all inner Vecs have the same length
-        (0..inner_length)
-            .map(|i| a.iter().all(|inner| inner[i]))
+            .collect();
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Label with the highest score, `None` if no label scores above zero. Ties go to the
+    /// `fallback` label if it is among them.
+    fn decide(scores: &[f64], fallback: Option<usize>) -> Option<usize> {
+        let best = scores.iter().copied().fold(0.0, f64::max);
+        if best <= 0.0 {
+            return None;
+        }
+        if fallback.is_some_and(|f| scores[f] == best) {
+            return fallback;
+        }
+        scores.iter().position(|&x| x == best)
+    }
+
+    fn label_index(&self, label: &str) -> LadResult<usize> {
+        self.labels
+            .iter()
+            .position(|x| x == label)
+            .ok_or_else(|| LadError::UnknownLabel(label.to_string()))
+    }
+
+    pub(crate) fn to_model(&self) -> LadResult<RuleGeneratorModel> {
+        let rules = self
+            .rules
+            .iter()
+            .map(|x| RuleModel {
+                label: x.label.clone(),
+                literals: x.literals.iter().cloned().collect(),
+                coverage: x.coverage.clone(),
+                prevalence: x.prevalence,
+                homogeneity: x.homogeneity,
+                index: x.index,
+            })
+            .collect();
+        Ok(RuleGeneratorModel {
+            binarizer: self.bin.to_model()?,
+            max: self.max,
+            min_purity: self.min_purity,
+            min_coverage: self.min_coverage,
+            label_min_coverage: self.label_min_coverage.clone(),
+            max_patterns: self.max_patterns.clone(),
+            strategy: self.strategy,
+            pattern_type: self.pattern_type,
+            labels: self.labels.clone(),
+            classes: self
+                .fallback_label
+                .as_ref()
+                .map(|_| to_values(&self.classes))
+                .transpose()?,
+            priors: self.priors.clone(),
+            fallback: self.fallback.clone(),
+            fallback_label: self.fallback_label.clone(),
+            decision_mode: self.decision_mode,
+            max_duration: self.max_duration,
+            max_candidates_per_degree: self.max_candidates_per_degree,
+            rules,
+        })
+    }
+
+    pub(crate) fn from_model(model: RuleGeneratorModel) -> LadResult<Self> {
+        Ok(Self {
+            bin: Binarizer::from_model(model.binarizer)?,
+            max: model.max,
+            min_purity: model.min_purity,
+            min_coverage: model.min_coverage,
+            label_min_coverage: model.label_min_coverage,
+            max_patterns: model.max_patterns,
+            strategy: model.strategy,
+            pattern_type: model.pattern_type,
+            rules: model
+                .rules
+                .into_iter()
+                .map(|x| Pattern {
+                    label: x.label,
+                    degree: x.literals.len(),
+                    literals: x.literals.into_iter().collect(),
+                    coverage: x.coverage,
+                    prevalence: x.prevalence,
+                    homogeneity: x.homogeneity,
+                    index: x.index,
+                })
+                .collect(),
+            classes: match &model.classes {
+                Some(classes) => from_values(classes)?,
+                None => Series::new_empty(PlSmallStr::EMPTY, &DataType::Null),
+            },
+            labels: model.labels,
+            priors: model.priors,
+            fallback: model.fallback,
+            fallback_label: model.fallback_label,
+            decision_mode: model.decision_mode,
+            max_duration: model.max_duration,
+            max_candidates_per_degree: model.max_candidates_per_degree,
+            cancellation_token: None,
+            observer: None,
+        })
+    }
+
+    fn emit(&self, event: &Event<'_>) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(event);
+        }
+    }
+
+    /// Label with the most weight in `groups`.
+    fn largest(&self, groups: &[Bitset], weights: Option<&[f64]>) -> Option<String> {
+        groups
+            .iter()
+            .map(|x| x.weight(weights))
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| self.labels[i].clone())
+    }
+
+    fn divide_data(&self, labels: &Series) -> LadResult<Vec<Bitset>> {
+        let groups = self.rows_of(labels);
+        if let Some(i) = groups.iter().position(|x| x.count() == 0) {
+            return Err(LadError::EmptyClass(self.labels[i].clone()));
+        }
+        Ok(groups)
+    }
+
+    /// Rows of every label, in the order of `labels`.
+    fn rows_of(&self, labels: &Series) -> Vec<Bitset> {
+        let labels = labels.iter().map(|x| label_text(&x)).collect::<Vec<_>>();
+        self.labels
+            .iter()
+            .map(|value| Bitset::from_bools(labels.iter().map(|x| x == value)))
             .collect()
     }
+}
 
-    fn divide_data(&self, data: &DataFrame, labels: &Series) -> Vec<DataFrame> {
-        let mut grouped_dfs: Vec<DataFrame> = Vec::new();
+/// Text of a label, strings without the quotes polars prints around them.
+pub(crate) fn label_text(value: &AnyValue<'_>) -> String {
+    value
+        .get_str()
+        .map_or_else(|| value.to_string(), ToString::to_string)
+}
 
-        let unique_y = self.labels.clone();
+/// Literals holding on every row `literals` cover in `data`, unchanged if they cover none.
+fn spanned(data: &BitMatrix, features: &[String], literals: &Literals) -> LadResult<Literals> {
+    let covered = data.coverage(literals)?;
+    let n = covered.count();
+    if n == 0 {
+        return Ok(literals.clone());
+    }
+    let mut spanned = Literals::new();
+    for feature in features {
+        for value in [true, false] {
+            if covered.and_count(data.literal(value, feature)?) == n {
+                spanned.insert((value, feature.clone()));
+            }
+        }
+    }
+    Ok(spanned)
+}
 
-        //println!("Debug1");
-        // Iterate through unique y values
-        for value in &unique_y {
-            // Filter the DataFrame rows where y equals the current unique value
-            let mask = labels.iter().map(|x| x.to_string() == *value).collect();
-            let sub_df = match data.filter(&mask) {
-                Ok(a) => a,
-                Err(e) => {
-                    eprintln!("{e:?}");
-                    continue;
+/// Text of a literal on the binarized column `column`, negating the column name when `value` is
+/// false. Nominal levels are shown without the quotes of string columns.
+fn describe(value: bool, column: &str) -> String {
+    let negate = |text: String| if value { column.to_string() } else { text };
+    if let Some(feature) = column.strip_suffix(" is null") {
+        negate(format!("{feature} is not null"))
+    } else if column.contains(" <= ") {
+        negate(format!("not ({column})"))
+    } else if let Some((feature, cutpoint)) = column.rsplit_once(" > ") {
+        negate(format!("{feature} <= {cutpoint}"))
+    } else if let Some((feature, level)) = column.split_once(" = ") {
+        let level = level
+            .strip_prefix('"')
+            .and_then(|x| x.strip_suffix('"'))
+            .unwrap_or(level);
+        let operator = if value { "=" } else { "!=" };
+        format!("{feature} {operator} {level}")
+    } else {
+        negate(format!("not {column}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binarization::Random;
+    use crate::NullPolicy;
+
+    /// Random boolean features, labelled by a function mixing parity and conjunction so that
+    /// patterns overlap across labels.
+    fn data(seed: u64, rows: usize) -> (DataFrame, Series) {
+        let mut random = Random(seed);
+        let columns = (0..6)
+            .map(|_| (0..rows).map(|_| random.below(2) == 1).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let labels = (0..rows)
+            .map(|r| (columns[0][r] ^ columns[1][r]) ^ (columns[2][r] && columns[3][r]))
+            .collect::<Vec<_>>();
+        let data = DataFrame::new(
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, x)| Series::new(format!("f{i}").into(), x))
+                .collect(),
+        )
+        .unwrap();
+        (data, Series::new("label".into(), labels))
+    }
+
+    #[test]
+    fn strong_patterns_keep_training_predictions() {
+        for seed in 0..20 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            for strategy in [
+                PatternStrategy::BottomUp,
+                PatternStrategy::TopDown,
+                PatternStrategy::MaxCoverage,
+            ] {
+                let predict = |pattern_type| {
+                    let mut model = RuleGenerator::new(&bin, 3)
+                        .with_strategy(strategy)
+                        .with_pattern_type(pattern_type);
+                    model.fit(&data, &labels, None).unwrap();
+                    (model.predict(&data).unwrap(), model.get_rules().len())
+                };
+                let (prime, n_prime) = predict(PatternType::Prime);
+                let (strong, n_strong) = predict(PatternType::Strong);
+                assert!(
+                    prime.equals(&strong),
+                    "seed {seed}, {strategy:?}: {prime} {strong}"
+                );
+                assert!(n_strong <= n_prime);
+            }
+        }
+    }
+
+    #[test]
+    fn max_coverage_patterns_are_pure_on_all_rows() {
+        for seed in 0..20 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            let mut model = RuleGenerator::new(&bin, 3).with_strategy(PatternStrategy::MaxCoverage);
+            model.fit(&data, &labels, None).unwrap();
+            for rule in model.get_rules() {
+                assert_eq!(rule.homogeneity, 1.0, "seed {seed}: {rule:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn labels_out_of_budget_leave_extensions_to_other_labels() {
+        // Both literals true on a "b" row are homogeneous for "a" at a purity of 0.7, and only
+        // their extensions single out the "b" rows.
+        let data = df!(
+            "f0" => [true, true, true, true, false, false, false],
+            "f1" => [false, false, false, true, false, true, true],
+        )
+        .unwrap();
+        let labels = Series::new("label".into(), ["a", "a", "a", "b", "b", "a", "a"]);
+        let mut bin = Binarizer::new(0.0, 0, 1);
+        bin.generate_cutpoints(&data, &labels, None).unwrap();
+        let mut model = RuleGenerator::new(&bin, 2)
+            .with_min_purity(0.7)
+            .with_max_patterns("a", 0);
+        model.fit(&data, &labels, None).unwrap();
+        assert!(model.get_rules().iter().all(|x| x.label == "b"));
+        assert!(model.predict(&data).unwrap().equals(&labels));
+    }
+
+    #[test]
+    fn predict_proba_peaks_at_the_prediction() {
+        for seed in 0..10 {
+            let (train, labels) = data(seed, 40);
+            // Rules of several labels only cover the same rows on unseen data
+            let (test, _) = data(seed + 100, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&train, &labels, None).unwrap();
+            for mode in [
+                DecisionMode::FirstMatch,
+                DecisionMode::MajorityVote,
+                DecisionMode::WeightedDiscriminant,
+            ] {
+                let mut model = RuleGenerator::new(&bin, 2).with_decision_mode(mode);
+                model.fit(&train, &labels, None).unwrap();
+                let proba = model.predict_proba(&test).unwrap();
+                let predicted = model.predict(&test).unwrap();
+                for (row, label) in predicted.iter().enumerate() {
+                    let proba = proba
+                        .get_columns()
+                        .iter()
+                        .map(|x| x.f64().unwrap().get(row).unwrap())
+                        .collect::<Vec<_>>();
+                    let i = model.label_index(&label_text(&label)).unwrap();
+                    // Ties aside, the predicted label is the argmax
+                    let best = proba.iter().copied().fold(0.0, f64::max);
+                    assert_eq!(proba[i], best, "seed {seed}, {mode:?}, row {row}");
+                    if mode == DecisionMode::FirstMatch {
+                        assert_eq!(proba[i], 1.0, "seed {seed}, row {row}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pruning_keeps_right_first_match_predictions() {
+        for seed in 0..20 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            for mode in [SelectionMode::Greedy, SelectionMode::Exact] {
+                // Impure rules cover training rows of other labels
+                let mut model = RuleGenerator::new(&bin, 3).with_min_purity(0.8);
+                model.fit(&data, &labels, None).unwrap();
+                let before = model.predict(&data).unwrap();
+                model.prune(&data, &labels, mode).unwrap();
+                let after = model.predict(&data).unwrap();
+                for row in 0..data.height() {
+                    let label = labels.get(row).unwrap();
+                    if before.get(row).unwrap() == label {
+                        assert_eq!(
+                            after.get(row).unwrap(),
+                            label,
+                            "seed {seed}, {mode:?}, row {row}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Cancels its token once every training row is covered, so that only the work after the
+    /// search is cut short.
+    struct CancelWhenCovered(CancellationToken);
+
+    impl Observer for CancelWhenCovered {
+        fn on_event(&self, event: &Event<'_>) {
+            if let Event::Remaining { counts, .. } = event {
+                if counts.iter().sum::<usize>() == 0 {
+                    self.0.cancel();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn limits_stop_spanning_and_strong_patterns() {
+        for seed in 0..10 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            let fit = |pattern_type, token: Option<CancellationToken>| {
+                let mut model = RuleGenerator::new(&bin, 4).with_pattern_type(pattern_type);
+                if let Some(token) = token {
+                    model = model
+                        .with_cancellation_token(token.clone())
+                        .with_observer(Arc::new(CancelWhenCovered(token)));
                 }
+                let report = model.fit(&data, &labels, None).unwrap();
+                let rules = model.get_rules().into_iter().map(|x| x.literals);
+                (report.stop_reason, rules.collect::<Vec<_>>())
             };
-            // Add the resulting sub DataFrame to the Vec
-            grouped_dfs.push(sub_df);
+            let (reason, prime) = fit(PatternType::Prime, None);
+            assert_eq!(reason, StopReason::Completed, "seed {seed}");
+            for pattern_type in [PatternType::Spanned, PatternType::Strong] {
+                let (reason, rules) = fit(pattern_type, Some(CancellationToken::new()));
+                assert_eq!(
+                    reason,
+                    StopReason::Cancelled,
+                    "seed {seed}, {pattern_type:?}"
+                );
+                assert_eq!(rules, prime, "seed {seed}, {pattern_type:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn pruning_rejects_unseen_labels() {
+        let (data, labels) = data(0, 40);
+        let mut bin = Binarizer::new(0.0, 0, 1);
+        bin.generate_cutpoints(&data, &labels, None).unwrap();
+        let mut model = RuleGenerator::new(&bin, 3);
+        model.fit(&data, &labels, None).unwrap();
+        let other = Series::new("label".into(), vec![Some("maybe"); 40]);
+        assert!(matches!(
+            model.prune(&data, &other, SelectionMode::Greedy),
+            Err(LadError::UnknownLabel(label)) if label == "maybe"
+        ));
+        let missing = Series::new("label".into(), vec![None::<bool>; 40]);
+        assert!(matches!(
+            model.prune(&data, &missing, SelectionMode::Greedy),
+            Err(LadError::MissingLabels(40))
+        ));
+    }
+
+    /// A numeric column labelled by its value, then rows missing it.
+    fn missing_data() -> (DataFrame, Series) {
+        let x = (0..40)
+            .map(|i| Some(f64::from(i % 2)))
+            .chain([None; 4])
+            .collect::<Vec<_>>();
+        let labels = x
+            .iter()
+            .map(|x| if *x == Some(1.0) { "b" } else { "a" })
+            .collect::<Vec<_>>();
+        let data = DataFrame::new(vec![Series::new("x".into(), x)]).unwrap();
+        (data, Series::new("label".into(), labels))
+    }
+
+    #[test]
+    fn unknown_values_neither_match_nor_reject() {
+        let (data, labels) = missing_data();
+        let mut bin = Binarizer::new(0.0, 0, 1).with_null_policy(NullPolicy::Unknown);
+        bin.generate_cutpoints(&data, &labels, None).unwrap();
+        let binary = bin.transform(&data).unwrap();
+        assert_eq!(binary.get_column_names(), ["x > 0.5"]);
+        assert_eq!(binary[0].null_count(), 4);
+        let mut model = RuleGenerator::new(&bin, 1);
+        model.fit(&binary, &labels, None).unwrap();
+        // Rules use the feature and its negation alike, and cover no row missing it
+        let rules = model.get_rules();
+        assert!(rules
+            .iter()
+            .any(|x| x.literals.contains(&(true, "x > 0.5".into()))));
+        assert!(rules
+            .iter()
+            .any(|x| x.literals.contains(&(false, "x > 0.5".into()))));
+        let predictions = model.predict(&data).unwrap();
+        for (row, explanation) in model.explain(&data).unwrap().iter().enumerate() {
+            assert_eq!(explanation.used_fallback, row >= 40, "row {row}");
+            assert_eq!(explanation.rules.is_empty(), row >= 40, "row {row}");
+            if row < 40 {
+                assert_eq!(predictions.get(row).unwrap(), labels.get(row).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn dropped_rows_get_the_fallback_label() {
+        let (data, labels) = missing_data();
+        let mut bin = Binarizer::new(0.0, 0, 1).with_null_policy(NullPolicy::Drop);
+        bin.generate_cutpoints(&data, &labels, None).unwrap();
+        let kept = bin.kept_rows(&data).unwrap();
+        assert_eq!(kept.sum(), Some(40));
+        let binary = bin.transform(&data).unwrap();
+        assert_eq!(binary.shape(), (40, 1));
+        let mut model =
+            RuleGenerator::new(&bin, 1).with_fallback(FallbackPolicy::Fixed("b".into()));
+        model
+            .fit(&binary, &labels.filter(&kept).unwrap(), None)
+            .unwrap();
+        let predictions = model.predict(&data).unwrap();
+        assert_eq!(predictions.len(), 44);
+        for row in 0..44 {
+            let expected = if row < 40 {
+                labels.get(row).unwrap()
+            } else {
+                "b".into()
+            };
+            assert_eq!(predictions.get(row).unwrap(), expected, "row {row}");
+        }
+    }
+
+    #[test]
+    fn weights_count_like_repeated_rows() {
+        for seed in 0..10 {
+            let (data, labels) = data(seed, 30);
+            let mut random = Random(seed + 100);
+            let weights = (0..30).map(|_| random.below(2) + 1).collect::<Vec<_>>();
+            let repeated = weights
+                .iter()
+                .enumerate()
+                .flat_map(|(row, &weight)| vec![row as IdxSize; weight as usize])
+                .collect::<Vec<_>>();
+            let repeated = IdxCa::from_vec("row".into(), repeated);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            let fit = |data: &DataFrame, labels: &Series, weights: Option<&Series>| {
+                let mut model = RuleGenerator::new(&bin, 3)
+                    .with_min_purity(0.8)
+                    .with_decision_mode(DecisionMode::WeightedDiscriminant)
+                    .with_fallback(FallbackPolicy::Priors);
+                model.fit(data, labels, weights).unwrap();
+                model
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let weights = Series::new(
+                "weight".into(),
+                weights.iter().map(|&x| x as f64).collect::<Vec<_>>(),
+            );
+            let weighted = fit(&data, &labels, Some(&weights));
+            let copied = fit(
+                &data.take(&repeated).unwrap(),
+                &labels.take(&repeated).unwrap(),
+                None,
+            );
+            let rules = |model: &RuleGenerator| {
+                model
+                    .get_rules()
+                    .into_iter()
+                    .map(|x| (x.label, x.literals, x.coverage, x.prevalence, x.homogeneity))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(rules(&weighted), rules(&copied), "seed {seed}");
+            let proba = weighted.predict_proba(&data).unwrap();
+            assert!(
+                proba.equals(&copied.predict_proba(&data).unwrap()),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn explanations_list_the_covering_rules() {
+        for seed in 0..10 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            for decision_mode in [
+                DecisionMode::FirstMatch,
+                DecisionMode::MajorityVote,
+                DecisionMode::WeightedDiscriminant,
+            ] {
+                let mut model = RuleGenerator::new(&bin, 2)
+                    .with_min_purity(0.8)
+                    .with_decision_mode(decision_mode);
+                model.fit(&data, &labels, None).unwrap();
+                let rules = model.get_rules();
+                let predictions = model.predict(&data).unwrap();
+                let explanations = model.explain(&data).unwrap();
+                for (row, explanation) in explanations.iter().enumerate() {
+                    let context = format!("seed {seed}, {decision_mode:?}, row {row}");
+                    let covering = rules
+                        .iter()
+                        .filter(|x| {
+                            x.literals.iter().all(|(value, column)| {
+                                data.column(column).unwrap().get(row).unwrap()
+                                    == AnyValue::Boolean(*value)
+                            })
+                        })
+                        .map(|x| x.index)
+                        .collect::<Vec<_>>();
+                    let fired = explanation
+                        .rules
+                        .iter()
+                        .map(|x| x.index)
+                        .collect::<Vec<_>>();
+                    assert_eq!(fired, covering, "{context}");
+                    assert_eq!(
+                        explanation.label,
+                        label_text(&predictions.get(row).unwrap()),
+                        "{context}"
+                    );
+                    if covering.is_empty() {
+                        assert!(explanation.used_fallback, "{context}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn literals_read_as_conditions() {
+        assert_eq!(describe(true, "age > 42.5"), "age > 42.5");
+        assert_eq!(describe(false, "age > 42.5"), "age <= 42.5");
+        assert_eq!(describe(false, "0.5 < x <= 1.5"), "not (0.5 < x <= 1.5)");
+        assert_eq!(describe(true, "color = \"red\""), "color = red");
+        assert_eq!(describe(false, "color = \"red\""), "color != red");
+        assert_eq!(describe(false, "age is null"), "age is not null");
+    }
+
+    #[test]
+    fn fallback_policies_label_uncovered_rows() {
+        for seed in 0..10 {
+            let (data, labels) = data(seed, 40);
+            let labels_text = labels.iter().map(|x| label_text(&x)).collect::<Vec<_>>();
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            // Label with strictly the most of `rows`, if any
+            let most = |rows: &[usize]| {
+                let count = |label: &str| rows.iter().filter(|&&r| labels_text[r] == label).count();
+                match count("true").cmp(&count("false")) {
+                    std::cmp::Ordering::Greater => Some("true"),
+                    std::cmp::Ordering::Less => Some("false"),
+                    std::cmp::Ordering::Equal => None,
+                }
+            };
+            for fallback in [
+                FallbackPolicy::MostRemaining,
+                FallbackPolicy::Priors,
+                FallbackPolicy::Fixed("false".into()),
+            ] {
+                // Rules of degree one leave rows uncovered
+                let mut model = RuleGenerator::new(&bin, 1).with_fallback(fallback.clone());
+                let report = model.fit(&data, &labels, None).unwrap();
+                let rules = model.get_rules();
+                let remaining = (0..40)
+                    .filter(|&row| {
+                        !rules.iter().any(|x| {
+                            x.label == labels_text[row]
+                                && x.literals.iter().all(|(value, column)| {
+                                    data.column(column).unwrap().get(row).unwrap()
+                                        == AnyValue::Boolean(*value)
+                                })
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(report.uncovered_rows, remaining.len(), "seed {seed}");
+                let expected = match &fallback {
+                    FallbackPolicy::MostRemaining if !remaining.is_empty() => most(&remaining),
+                    FallbackPolicy::MostRemaining | FallbackPolicy::Priors => {
+                        most(&(0..40).collect::<Vec<_>>())
+                    }
+                    FallbackPolicy::Fixed(label) => Some(label.as_str()),
+                };
+                let shares = model
+                    .get_labels()
+                    .iter()
+                    .map(|label| labels_text.iter().filter(|&x| x == label).count() as f64 / 40.0)
+                    .collect::<Vec<_>>();
+                let predictions = model.predict(&data).unwrap();
+                let proba = model.predict_proba(&data).unwrap();
+                let explanations = model.explain(&data).unwrap();
+                for (row, explanation) in explanations.iter().enumerate() {
+                    if !explanation.rules.is_empty() {
+                        continue;
+                    }
+                    let context = format!("seed {seed}, {fallback:?}, row {row}");
+                    let label = label_text(&predictions.get(row).unwrap());
+                    if let Some(expected) = expected {
+                        assert_eq!(label, expected, "{context}");
+                    }
+                    let row = proba
+                        .get_columns()
+                        .iter()
+                        .map(|x| x.f64().unwrap().get(row).unwrap())
+                        .collect::<Vec<_>>();
+                    if fallback == FallbackPolicy::Priors {
+                        assert_eq!(row, shares, "{context}");
+                    } else {
+                        let i = model.label_index(&label).unwrap();
+                        assert_eq!(row[i], 1.0, "{context}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn budgets_cap_the_rules_of_a_label() {
+        for seed in 0..10 {
+            let (data, labels) = data(seed, 40);
+            let mut bin = Binarizer::new(0.0, 0, 1);
+            bin.generate_cutpoints(&data, &labels, None).unwrap();
+            for strategy in [
+                PatternStrategy::BottomUp,
+                PatternStrategy::TopDown,
+                PatternStrategy::MaxCoverage,
+            ] {
+                let mut model = RuleGenerator::new(&bin, 3)
+                    .with_strategy(strategy)
+                    .with_max_patterns("true", 1);
+                model.fit(&data, &labels, None).unwrap();
+                let rules = model.get_rules();
+                let count = |label: &str| rules.iter().filter(|x| x.label == label).count();
+                assert!(count("true") <= 1, "seed {seed}, {strategy:?}");
+                assert!(count("false") > 1, "seed {seed}, {strategy:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn limits_stop_the_search() {
+        let (data, labels) = data(0, 40);
+        let mut bin = Binarizer::new(0.0, 0, 1);
+        bin.generate_cutpoints(&data, &labels, None).unwrap();
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        for strategy in [
+            PatternStrategy::BottomUp,
+            PatternStrategy::TopDown,
+            PatternStrategy::MaxCoverage,
+        ] {
+            let model = || RuleGenerator::new(&bin, 3).with_strategy(strategy);
+            for (mut model, reason) in [
+                (
+                    model().with_max_duration(Duration::ZERO),
+                    StopReason::MaxDuration,
+                ),
+                (
+                    model().with_cancellation_token(cancelled.clone()),
+                    StopReason::Cancelled,
+                ),
+            ] {
+                let report = model.fit(&data, &labels, None).unwrap();
+                assert_eq!(report.stop_reason, reason, "{strategy:?}");
+                // Stopped before finding any rule, yet still predicting
+                assert!(model.get_rules().is_empty(), "{strategy:?}, {reason:?}");
+                assert_eq!(report.uncovered_rows, 40, "{strategy:?}, {reason:?}");
+                assert_eq!(model.predict(&data).unwrap().len(), 40);
+            }
         }
-        grouped_dfs
+        let mut model = RuleGenerator::new(&bin, 3).with_max_candidates_per_degree(1);
+        let report = model.fit(&data, &labels, None).unwrap();
+        assert_eq!(report.stop_reason, StopReason::MaxCandidates);
+        assert!(report.uncovered_rows > 0);
     }
 }
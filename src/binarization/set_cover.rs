@@ -0,0 +1,186 @@
+//! Set covering solvers shared by support set selection and theory pruning.
+//!
+//! Elements are given as the list of sets covering them, elements no set covers must be left out
+//! by the caller. Both solvers return the chosen set indices in increasing order.
+
+/// Repeatedly picks the set covering the most uncovered elements, lowest index on ties.
+pub(crate) fn greedy(elements: &[Vec<usize>], n_sets: usize) -> Vec<usize> {
+    let mut covered = vec![false; elements.len()];
+    let mut remaining = elements.len();
+    let mut chosen = Vec::new();
+    while remaining > 0 {
+        let mut gains = vec![0usize; n_sets];
+        for (sets, _) in elements.iter().zip(&covered).filter(|(_, &c)| !c) {
+            for &s in sets {
+                gains[s] += 1;
+            }
+        }
+        let Some((best, _)) = gains
+            .iter()
+            .enumerate()
+            .filter(|(_, &g)| g > 0)
+            .max_by(|(i, a), (j, b)| a.cmp(b).then(j.cmp(i)))
+        else {
+            break;
+        };
+        for (sets, c) in elements.iter().zip(covered.iter_mut()) {
+            if !*c && sets.contains(&best) {
+                *c = true;
+                remaining -= 1;
+            }
+        }
+        chosen.push(best);
+    }
+    chosen.sort_unstable();
+    chosen
+}
+
+/// Branch and bound search for a minimum cover, seeded with the greedy solution.
+///
+/// The search is exponential in the worst case and meant for small instances.
+pub(crate) fn exact(elements: &[Vec<usize>], n_sets: usize) -> Vec<usize> {
+    let mut search = Search {
+        elements,
+        covers: vec![Vec::new(); n_sets],
+        covered: vec![0; elements.len()],
+        forbidden: vec![false; n_sets],
+        chosen: Vec::new(),
+        best: greedy(elements, n_sets),
+    };
+    for (e, sets) in elements.iter().enumerate() {
+        for &s in sets {
+            search.covers[s].push(e);
+        }
+    }
+    search.run();
+    let mut best = search.best;
+    best.sort_unstable();
+    best
+}
+
+struct Search<'a> {
+    elements: &'a [Vec<usize>],
+    covers: Vec<Vec<usize>>,
+    covered: Vec<u32>,
+    forbidden: Vec<bool>,
+    chosen: Vec<usize>,
+    best: Vec<usize>,
+}
+
+impl Search<'_> {
+    fn run(&mut self) {
+        // Branch on the uncovered element with the fewest remaining options.
+        let mut branch: Option<Vec<usize>> = None;
+        for (e, sets) in self.elements.iter().enumerate() {
+            if self.covered[e] > 0 {
+                continue;
+            }
+            let options = sets
+                .iter()
+                .copied()
+                .filter(|&s| !self.forbidden[s])
+                .collect::<Vec<_>>();
+            if options.is_empty() {
+                return;
+            }
+            if branch.as_ref().is_none_or(|b| options.len() < b.len()) {
+                branch = Some(options);
+            }
+        }
+        let Some(options) = branch else {
+            if self.chosen.len() < self.best.len() {
+                self.best.clone_from(&self.chosen);
+            }
+            return;
+        };
+        if self.chosen.len() + 1 >= self.best.len() {
+            return;
+        }
+
+        // Sets tried in earlier branches are excluded from later ones, so every cover is visited
+        // at most once.
+        for &s in &options {
+            self.chosen.push(s);
+            for &e in &self.covers[s] {
+                self.covered[e] += 1;
+            }
+            self.run();
+            for &e in &self.covers[s] {
+                self.covered[e] -= 1;
+            }
+            self.chosen.pop();
+            self.forbidden[s] = true;
+        }
+        for &s in &options {
+            self.forbidden[s] = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binarization::Random;
+
+    /// Random instances where every element is covered by at least one of `n_sets` sets.
+    fn instances(n_sets: usize) -> impl Iterator<Item = Vec<Vec<usize>>> {
+        let mut random = Random(11);
+        let mut below = move |n| random.below(n);
+        (0..200).map(move |_| {
+            let n_elements = 1 + below(12) as usize;
+            (0..n_elements)
+                .map(|_| {
+                    let mut sets = (0..n_sets).filter(|_| below(4) == 0).collect::<Vec<_>>();
+                    if sets.is_empty() {
+                        sets.push(below(n_sets as u64) as usize);
+                    }
+                    sets
+                })
+                .collect()
+        })
+    }
+
+    fn covers(elements: &[Vec<usize>], chosen: &[usize]) -> bool {
+        elements
+            .iter()
+            .all(|sets| sets.iter().any(|s| chosen.contains(s)))
+    }
+
+    /// Size of the smallest cover, trying every subset of the sets.
+    fn brute_force(elements: &[Vec<usize>], n_sets: usize) -> usize {
+        (0..1usize << n_sets)
+            .filter_map(|mask| {
+                let chosen = (0..n_sets)
+                    .filter(|s| mask >> s & 1 == 1)
+                    .collect::<Vec<_>>();
+                covers(elements, &chosen).then_some(chosen.len())
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn exact_finds_a_minimum_cover() {
+        let n_sets = 8;
+        for (case, elements) in instances(n_sets).enumerate() {
+            let chosen = exact(&elements, n_sets);
+            assert!(covers(&elements, &chosen), "case {case}");
+            assert!(chosen.windows(2).all(|x| x[0] < x[1]), "case {case}");
+            assert_eq!(chosen.len(), brute_force(&elements, n_sets), "case {case}");
+        }
+    }
+
+    #[test]
+    fn greedy_finds_a_cover() {
+        let n_sets = 8;
+        for (case, elements) in instances(n_sets).enumerate() {
+            let chosen = greedy(&elements, n_sets);
+            assert!(covers(&elements, &chosen), "case {case}");
+            assert!(chosen.windows(2).all(|x| x[0] < x[1]), "case {case}");
+            assert!(
+                chosen.len() >= exact(&elements, n_sets).len(),
+                "case {case}"
+            );
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use polars::prelude::*;
+
+use super::binarize::Binarizer;
+use super::rule_generation::label_text;
+use super::set_cover;
+use crate::error::{LadError, LadResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Fast approximation of the smallest support set.
+    Greedy,
+    /// Smallest support set, exponential in the worst case.
+    Exact,
+}
+
+/// Prunes the cutpoints of a fitted [`Binarizer`] down to a support set: a small set of
+/// binary attributes that still tells apart every pair of observations with different labels.
+///
+/// Nominal and boolean features are always kept, only cutpoints are candidates for removal.
+pub struct SupportSetSelector {
+    mode: SelectionMode,
+}
+
+impl SupportSetSelector {
+    pub const fn new(mode: SelectionMode) -> Self {
+        Self { mode }
+    }
+
+    pub fn select(
+        &self,
+        binarizer: &mut Binarizer,
+        data: &DataFrame,
+        label: &Series,
+    ) -> LadResult<()> {
+        if data.height() != label.len() {
+            return Err(LadError::LengthMismatch {
+                data: data.height(),
+                label: label.len(),
+            });
+        }
+        // Interval features are derived from the cutpoints, so only level features take part.
+        let binary = binarizer.clone().with_intervals(false).transform(data)?;
+        let label = label.filter(&binarizer.kept_rows(data)?)?;
+        let cutpoints = binarizer.get_cutpoints();
+
+        let mut candidates = Vec::new();
+        let mut candidate_names = HashSet::new();
+        for (i, feature) in cutpoints.iter().enumerate() {
+            for (j, cutpoint) in feature.iter().enumerate() {
+                let name = format!("{} > {cutpoint}", feature.name());
+                let column = binary
+                    .column(&name)
+                    .map_err(|_| LadError::ColumnNotFound(name.clone()))?;
+                candidates.push(((i, j), bits(column)?));
+                candidate_names.insert(name);
+            }
+        }
+        let fixed = binary
+            .get_columns()
+            .iter()
+            .filter(|x| !candidate_names.contains(x.name().as_str()))
+            .map(bits)
+            .collect::<LadResult<Vec<_>>>()?;
+
+        // Identical observations only need to be looked at once, and only need separating from
+        // differently labelled ones that agree on every fixed attribute.
+        let words = candidates.len().div_ceil(64);
+        let mut classes = HashSet::new();
+        for (row, l) in label.iter().enumerate() {
+            let key = fixed.iter().map(|x| x[row]).collect::<Vec<_>>();
+            let mut word = vec![0u64; words];
+            for (k, (_, column)) in candidates.iter().enumerate() {
+                if column[row] {
+                    word[k / 64] |= 1 << (k % 64);
+                }
+            }
+            classes.insert((key, label_text(&l), word));
+        }
+        let mut groups: HashMap<Vec<bool>, Vec<(String, Vec<u64>)>> = HashMap::new();
+        for (key, label, word) in classes {
+            groups.entry(key).or_default().push((label, word));
+        }
+
+        let mut differences = HashSet::new();
+        for group in groups.values() {
+            for (a, (label_a, word_a)) in group.iter().enumerate() {
+                for (label_b, word_b) in &group[a + 1..] {
+                    if label_a == label_b {
+                        continue;
+                    }
+                    let difference = word_a
+                        .iter()
+                        .zip(word_b)
+                        .map(|(x, y)| x ^ y)
+                        .collect::<Vec<_>>();
+                    // Identical observations with different labels cannot be separated.
+                    if difference.iter().any(|&x| x != 0) {
+                        differences.insert(difference);
+                    }
+                }
+            }
+        }
+
+        let mut elements = differences
+            .into_iter()
+            .map(|difference| {
+                (0..candidates.len())
+                    .filter(|k| difference[k / 64] >> (k % 64) & 1 == 1)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        elements.sort_unstable();
+        let selected = match self.mode {
+            SelectionMode::Greedy => set_cover::greedy(&elements, candidates.len()),
+            SelectionMode::Exact => set_cover::exact(&elements, candidates.len()),
+        };
+
+        let mut kept = vec![Vec::new(); cutpoints.len()];
+        for k in selected {
+            let (i, j) = candidates[k].0;
+            kept[i].push(j);
+        }
+        let cutpoints = cutpoints
+            .iter()
+            .zip(kept)
+            .map(|(feature, mut kept)| {
+                kept.sort_unstable();
+                let idx = kept.into_iter().map(|x| x as IdxSize).collect::<Vec<_>>();
+                Ok(feature.take_slice(&idx)?)
+            })
+            .collect::<LadResult<Vec<_>>>()?;
+        binarizer.set_cutpoints(cutpoints);
+        Ok(())
+    }
+}
+
+fn bits(column: &Series) -> LadResult<Vec<bool>> {
+    Ok(column
+        .bool()?
+        .into_iter()
+        .map(|x| x.unwrap_or(false))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binarization::Random;
+
+    /// Random numeric and nominal columns with three labels.
+    fn data(seed: u64, rows: usize) -> (DataFrame, Series) {
+        let mut random = Random(seed);
+        let mut below = |n| random.below(n);
+        let x = (0..rows).map(|_| below(20) as f64).collect::<Vec<_>>();
+        let z = (0..rows).map(|_| below(20) as f64).collect::<Vec<_>>();
+        let c = (0..rows)
+            .map(|_| ["a", "b"][below(2) as usize])
+            .collect::<Vec<_>>();
+        let labels = (0..rows).map(|_| below(3) as i32).collect::<Vec<_>>();
+        let data = DataFrame::new(vec![
+            Series::new("x".into(), x),
+            Series::new("z".into(), z),
+            Series::new("c".into(), c),
+        ])
+        .unwrap();
+        (data, Series::new("label".into(), labels))
+    }
+
+    /// Binarized rows, one vector of values per row.
+    fn rows(binarizer: &Binarizer, data: &DataFrame) -> Vec<Vec<bool>> {
+        let columns = binarizer
+            .transform(data)
+            .unwrap()
+            .get_columns()
+            .iter()
+            .map(|x| bits(x).unwrap())
+            .collect::<Vec<_>>();
+        (0..data.height())
+            .map(|row| columns.iter().map(|x| x[row]).collect())
+            .collect()
+    }
+
+    #[test]
+    fn support_set_separates_differently_labelled_rows() {
+        for seed in 0..20 {
+            let (data, label) = data(seed, 30);
+            let mut binarizer = Binarizer::new(f64::NEG_INFINITY, 2, 100);
+            binarizer.generate_cutpoints(&data, &label, None).unwrap();
+            let all = rows(&binarizer, &data);
+            let mut sizes = Vec::new();
+            for mode in [SelectionMode::Greedy, SelectionMode::Exact] {
+                let mut selected = binarizer.clone();
+                SupportSetSelector::new(mode)
+                    .select(&mut selected, &data, &label)
+                    .unwrap();
+                let kept = rows(&selected, &data);
+                for a in 0..data.height() {
+                    for b in a + 1..data.height() {
+                        if label.get(a).unwrap() != label.get(b).unwrap() && all[a] != all[b] {
+                            assert_ne!(kept[a], kept[b], "seed {seed}, {mode:?}, rows {a} {b}");
+                        }
+                    }
+                }
+                sizes.push(
+                    selected
+                        .get_cutpoints()
+                        .iter()
+                        .map(|x| x.len())
+                        .sum::<usize>(),
+                );
+            }
+            assert!(sizes[1] <= sizes[0], "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+use polars::prelude::PolarsError;
+
+pub type LadResult<T> = Result<T, LadError>;
+
+#[derive(Debug)]
+pub enum LadError {
+    /// Number of rows in the data and number of labels differ.
+    LengthMismatch {
+        data: usize,
+        label: usize,
+    },
+    /// A column the model relies on is missing from the input.
+    ColumnNotFound(String),
+    /// The model was used before being fitted.
+    NotFitted(&'static str),
+    /// A column has a data type the model cannot handle.
+    UnsupportedDtype {
+        column: String,
+        dtype: String,
+    },
+    /// A class ended up with no observations.
+    EmptyClass(String),
+    /// Training labels have missing values, counted here.
+    MissingLabels(usize),
+    /// A setting or the labels given after fitting name a label absent from the training labels.
+    UnknownLabel(String),
+    /// A model could not be encoded or decoded.
+    Serialization(String),
+    /// A decoded model failed validation.
+    InvalidModel(String),
+    /// Row weights are missing, negative, not finite or of the wrong length.
+    InvalidWeights(String),
+    /// A setting is outside the range it must be in.
+    InvalidParameter(String),
+    Polars(PolarsError),
+}
+
+impl fmt::Display for LadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { data, label } => {
+                write!(f, "lengths of data {data} and label {label} do not match")
+            }
+            Self::ColumnNotFound(column) => write!(
+                f,
+                "cannot find column {column}, make sure schema of input and output data is the same"
+            ),
+            Self::NotFitted(model) => write!(f, "{model} is not fitted yet"),
+            Self::UnsupportedDtype { column, dtype } => {
+                write!(f, "column {column} has unsupported data type {dtype}")
+            }
+            Self::EmptyClass(label) => write!(f, "class {label} has no observations"),
+            Self::MissingLabels(count) => write!(f, "labels have {count} missing values"),
+            Self::UnknownLabel(label) => write!(f, "label {label} is not a training label"),
+            Self::Serialization(message) => write!(f, "serialization failed: {message}"),
+            Self::InvalidModel(message) => write!(f, "invalid model: {message}"),
+            Self::InvalidWeights(message) => write!(f, "invalid weights: {message}"),
+            Self::InvalidParameter(message) => write!(f, "invalid parameter: {message}"),
+            Self::Polars(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for LadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Polars(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<PolarsError> for LadError {
+    fn from(e: PolarsError) -> Self {
+        Self::Polars(e)
+    }
+}
@@ -1,10 +1,18 @@
 pub mod binarization;
+pub mod error;
+pub mod persistence;
+pub mod progress;
+#[cfg(feature = "python")]
+mod python;
 
-pub use crate::binarization::{binarize::Binarizer, rule_generation::RuleGenerator};
-
-//#[pymodule]
-//pub fn lad_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
-//    m.add_class::<Binarizer>()?;
-//    m.add_class::<RuleGenerator>()?;
-//    Ok(())
-//}
+pub use crate::binarization::{
+    binarize::{Binarizer, CutpointCriterion, NullPolicy},
+    pattern::Pattern,
+    rule_generation::{
+        DecisionMode, Explanation, FallbackPolicy, FiredRule, FitReport, MinCoverage,
+        PatternStrategy, PatternType, PruneReport, RuleGenerator, StopReason,
+    },
+    support_set::{SelectionMode, SupportSetSelector},
+};
+pub use crate::error::{LadError, LadResult};
+pub use crate::progress::{CancellationToken, Event, Observer, StdoutObserver};
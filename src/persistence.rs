@@ -0,0 +1,700 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "categorical")]
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(feature = "categorical")]
+use polars::export::arrow::array::Utf8ViewArray;
+use polars::prelude::*;
+use polars_core::chunked_array::temporal::validate_time_zone;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::binarization::rule_generation::label_text;
+use crate::error::{LadError, LadResult};
+use crate::{
+    Binarizer, CutpointCriterion, DecisionMode, FallbackPolicy, MinCoverage, NullPolicy,
+    PatternStrategy, PatternType, RuleGenerator,
+};
+
+/// Version of the on-disk model format, bumped on every incompatible change.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"LADR";
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BinarizerModel {
+    pub(crate) threshold: f64,
+    pub(crate) nominal_size: usize,
+    pub(crate) max_cutpoints: usize,
+    pub(crate) intervals: bool,
+    pub(crate) null_policy: NullPolicy,
+    pub(crate) criterion: CutpointCriterion,
+    pub(crate) null_features: Vec<String>,
+    /// Levels of the nominal columns seen during fitting.
+    pub(crate) levels: Vec<ValuesModel>,
+    pub(crate) fitted: bool,
+    pub(crate) cutpoints: Vec<CutpointsModel>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CutpointsModel {
+    pub(crate) feature: String,
+    pub(crate) dtype: String,
+    pub(crate) values: Vec<f64>,
+}
+
+/// Labels or levels of a column, as [`to_values`] saves them.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ValuesModel {
+    pub(crate) name: String,
+    pub(crate) dtype: String,
+    /// Categories of categorical and enum columns, in the order of their physical values.
+    pub(crate) categories: Vec<String>,
+    /// Values as [`to_text`] writes them.
+    pub(crate) values: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RuleGeneratorModel {
+    pub(crate) binarizer: BinarizerModel,
+    pub(crate) max: usize,
+    pub(crate) min_purity: f64,
+    pub(crate) min_coverage: MinCoverage,
+    pub(crate) label_min_coverage: BTreeMap<String, MinCoverage>,
+    pub(crate) max_patterns: BTreeMap<String, usize>,
+    pub(crate) strategy: PatternStrategy,
+    pub(crate) pattern_type: PatternType,
+    pub(crate) labels: Vec<String>,
+    /// Training labels in the order of `labels`, `None` before fitting.
+    pub(crate) classes: Option<ValuesModel>,
+    pub(crate) priors: Vec<f64>,
+    pub(crate) fallback: FallbackPolicy,
+    pub(crate) fallback_label: Option<String>,
+    pub(crate) decision_mode: DecisionMode,
+    pub(crate) max_duration: Option<Duration>,
+    pub(crate) max_candidates_per_degree: Option<usize>,
+    pub(crate) rules: Vec<RuleModel>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RuleModel {
+    pub(crate) label: String,
+    pub(crate) literals: Vec<(bool, String)>,
+    pub(crate) coverage: Vec<f64>,
+    pub(crate) prevalence: f64,
+    pub(crate) homogeneity: f64,
+    /// Discovery index, which differs from the position once rules are pruned.
+    pub(crate) index: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<M> {
+    version: u32,
+    kind: String,
+    model: M,
+}
+
+trait Model: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+
+    fn validate(&self) -> LadResult<()>;
+}
+
+impl Model for BinarizerModel {
+    const KIND: &'static str = "binarizer";
+
+    fn validate(&self) -> LadResult<()> {
+        for cutpoints in &self.cutpoints {
+            let dtype = parse_dtype(&cutpoints.dtype)?;
+            if !dtype.is_numeric() {
+                return Err(invalid(format!(
+                    "cutpoints of {} have non numeric type {dtype}",
+                    cutpoints.feature
+                )));
+            }
+            if cutpoints.values.len() > self.max_cutpoints {
+                return Err(invalid(format!(
+                    "{} has {} cutpoints but at most {} are allowed",
+                    cutpoints.feature,
+                    cutpoints.values.len(),
+                    self.max_cutpoints
+                )));
+            }
+            if cutpoints.values.iter().any(|x| !x.is_finite()) {
+                return Err(invalid(format!(
+                    "{} has non finite cutpoints",
+                    cutpoints.feature
+                )));
+            }
+        }
+        for levels in &self.levels {
+            parse_value_dtype(&levels.dtype)?;
+        }
+        if !self.fitted && (!self.cutpoints.is_empty() || !self.levels.is_empty()) {
+            return Err(invalid(
+                "unfitted binarizer has cutpoints or levels".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Model for RuleGeneratorModel {
+    const KIND: &'static str = "rule_generator";
+
+    fn validate(&self) -> LadResult<()> {
+        self.binarizer.validate()?;
+        if !(0.0..=1.0).contains(&self.min_purity) {
+            return Err(invalid(format!(
+                "minimum purity {} outside [0, 1]",
+                self.min_purity
+            )));
+        }
+        for min_coverage in
+            std::iter::once(&self.min_coverage).chain(self.label_min_coverage.values())
+        {
+            if let MinCoverage::Fraction(fraction) = min_coverage {
+                if !(0.0..=1.0).contains(fraction) {
+                    return Err(invalid(format!(
+                        "minimum coverage {fraction} outside [0, 1]"
+                    )));
+                }
+            }
+        }
+        let known = |label: &String| self.labels.contains(label);
+        if let Some(label) = self.fallback_label.iter().find(|x| !known(x)) {
+            return Err(invalid(format!("unknown fallback label {label}")));
+        }
+        if self.fallback_label.is_some() {
+            let configured = self
+                .label_min_coverage
+                .keys()
+                .chain(self.max_patterns.keys());
+            let fixed = match &self.fallback {
+                FallbackPolicy::Fixed(label) => Some(label),
+                _ => None,
+            };
+            if let Some(label) = configured.chain(fixed).find(|x| !known(x)) {
+                return Err(invalid(format!("settings for unknown label {label}")));
+            }
+        }
+        if let Some(classes) = &self.classes {
+            parse_value_dtype(&classes.dtype)?;
+            if classes.values.len() != self.labels.len() {
+                return Err(invalid(format!(
+                    "{} values for {} labels",
+                    classes.values.len(),
+                    self.labels.len()
+                )));
+            }
+        }
+        if self.fallback_label.is_some() && self.classes.is_none() {
+            return Err(invalid("fitted rule generator has no labels".to_string()));
+        }
+        if !self.priors.is_empty()
+            && (self.priors.len() != self.labels.len()
+                || self.priors.iter().any(|x| !(0.0..=1.0).contains(x)))
+        {
+            return Err(invalid(format!(
+                "{} priors for {} labels, or priors outside [0, 1]",
+                self.priors.len(),
+                self.labels.len()
+            )));
+        }
+        for rule in &self.rules {
+            if !known(&rule.label) {
+                return Err(invalid(format!("rule for unknown label {}", rule.label)));
+            }
+            if rule.literals.is_empty() {
+                return Err(invalid(format!("empty rule for label {}", rule.label)));
+            }
+            for (name, value) in [
+                ("prevalence", rule.prevalence),
+                ("homogeneity", rule.homogeneity),
+            ] {
+                if !(0.0..=1.0).contains(&value) {
+                    return Err(invalid(format!(
+                        "rule for label {} has {name} {value} outside [0, 1]",
+                        rule.label
+                    )));
+                }
+            }
+            if rule.coverage.iter().any(|x| !x.is_finite() || *x < 0.0) {
+                return Err(invalid(format!(
+                    "rule for label {} has a negative or infinite coverage",
+                    rule.label
+                )));
+            }
+            if !rule.coverage.is_empty() && rule.coverage.len() != self.labels.len() {
+                return Err(invalid(format!(
+                    "rule for label {} has coverage for {} labels, expected {}",
+                    rule.label,
+                    rule.coverage.len(),
+                    self.labels.len()
+                )));
+            }
+        }
+        if self.fallback_label.is_none() && !self.rules.is_empty() {
+            return Err(invalid(
+                "fitted rule generator has no fallback label".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: String) -> LadError {
+    LadError::InvalidModel(message)
+}
+
+fn serialization(e: impl std::fmt::Display) -> LadError {
+    LadError::Serialization(e.to_string())
+}
+
+fn to_json<M: Model>(model: M) -> LadResult<String> {
+    let envelope = Envelope {
+        version: FORMAT_VERSION,
+        kind: M::KIND.to_string(),
+        model,
+    };
+    serde_json::to_string_pretty(&envelope).map_err(serialization)
+}
+
+fn from_json<M: Model>(json: &str) -> LadResult<M> {
+    let envelope: Envelope<serde_json::Value> =
+        serde_json::from_str(json).map_err(serialization)?;
+    check_version(envelope.version)?;
+    check_kind(&envelope.kind, M::KIND)?;
+    let model: M = serde_json::from_value(envelope.model).map_err(serialization)?;
+    model.validate()?;
+    Ok(model)
+}
+
+fn to_bytes<M: Model>(model: M) -> LadResult<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bincode::serialize_into(&mut bytes, &(M::KIND, model)).map_err(serialization)?;
+    Ok(bytes)
+}
+
+fn from_bytes<M: Model>(bytes: &[u8]) -> LadResult<M> {
+    let Some(bytes) = bytes.strip_prefix(MAGIC) else {
+        return Err(LadError::Serialization(
+            "not a lad_rs model, magic bytes missing".to_string(),
+        ));
+    };
+    let (version, payload) = bytes.split_at(bytes.len().min(4));
+    let version = u32::from_le_bytes(version.try_into().map_err(serialization)?);
+    check_version(version)?;
+    let mut payload = payload;
+    let kind: String = bincode::deserialize_from(&mut payload).map_err(serialization)?;
+    check_kind(&kind, M::KIND)?;
+    let model: M = bincode::deserialize_from(payload).map_err(serialization)?;
+    model.validate()?;
+    Ok(model)
+}
+
+fn check_version(version: u32) -> LadResult<()> {
+    if version != FORMAT_VERSION {
+        return Err(invalid(format!(
+            "format version {version} is not supported, expected {FORMAT_VERSION}"
+        )));
+    }
+    Ok(())
+}
+
+fn check_kind(kind: &str, expected: &str) -> LadResult<()> {
+    if kind != expected {
+        return Err(invalid(format!(
+            "expected a {expected} model, found {kind}"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_dtype(name: &str) -> LadResult<DataType> {
+    Ok(match name {
+        "u8" => DataType::UInt8,
+        "u16" => DataType::UInt16,
+        "u32" => DataType::UInt32,
+        "u64" => DataType::UInt64,
+        "i8" => DataType::Int8,
+        "i16" => DataType::Int16,
+        "i32" => DataType::Int32,
+        "i64" => DataType::Int64,
+        "f32" => DataType::Float32,
+        "f64" => DataType::Float64,
+        _ => return Err(invalid(format!("unknown data type {name}"))),
+    })
+}
+
+/// Data type of labels and nominal levels: numeric ones, strings, booleans, categoricals, enums,
+/// dates, datetimes or durations. Categoricals and enums come without their categories.
+pub(crate) fn parse_value_dtype(name: &str) -> LadResult<DataType> {
+    let unsupported = || invalid(format!("values of data type {name} are not supported"));
+    let time_unit = |unit: &str| match unit {
+        "ns" => Ok(TimeUnit::Nanoseconds),
+        "μs" => Ok(TimeUnit::Microseconds),
+        "ms" => Ok(TimeUnit::Milliseconds),
+        _ => Err(unsupported()),
+    };
+    Ok(match name {
+        "str" => DataType::String,
+        "bool" => DataType::Boolean,
+        "date" => DataType::Date,
+        #[cfg(feature = "categorical")]
+        "cat" => DataType::Categorical(None, CategoricalOrdering::Physical),
+        #[cfg(feature = "categorical")]
+        "cat[lexical]" => DataType::Categorical(None, CategoricalOrdering::Lexical),
+        #[cfg(feature = "categorical")]
+        "enum" => DataType::Enum(None, CategoricalOrdering::Physical),
+        _ => {
+            if let Some(unit) = name
+                .strip_prefix("duration[")
+                .and_then(|x| x.strip_suffix(']'))
+            {
+                DataType::Duration(time_unit(unit)?)
+            } else if let Some(inner) = name
+                .strip_prefix("datetime[")
+                .and_then(|x| x.strip_suffix(']'))
+            {
+                match inner.split_once(", ") {
+                    Some((unit, time_zone)) => {
+                        validate_time_zone(time_zone).map_err(|_| unsupported())?;
+                        DataType::Datetime(time_unit(unit)?, Some(time_zone.into()))
+                    }
+                    None => DataType::Datetime(time_unit(inner)?, None),
+                }
+            } else {
+                parse_dtype(name).map_err(|_| unsupported())?
+            }
+        }
+    })
+}
+
+/// Name of `dtype` as [`parse_value_dtype`] reads it, which tells lexically ordered
+/// categoricals apart.
+fn dtype_name(dtype: &DataType) -> String {
+    match dtype {
+        #[cfg(feature = "categorical")]
+        DataType::Categorical(_, CategoricalOrdering::Lexical) => "cat[lexical]".to_string(),
+        dtype => dtype.to_string(),
+    }
+}
+
+/// Saved form of `column`, an error if values of its data type cannot be saved.
+pub(crate) fn to_values(column: &Series) -> LadResult<ValuesModel> {
+    let dtype = dtype_name(column.dtype());
+    if parse_value_dtype(&dtype).is_err() {
+        return Err(LadError::UnsupportedDtype {
+            column: column.name().to_string(),
+            dtype,
+        });
+    }
+    Ok(ValuesModel {
+        name: column.name().to_string(),
+        dtype,
+        categories: categories(column)?,
+        values: to_text(column)?,
+    })
+}
+
+/// Column saved by [`to_values`].
+pub(crate) fn from_values(model: &ValuesModel) -> LadResult<Series> {
+    let dtype = parse_value_dtype(&model.dtype)?;
+    if dtype.is_categorical() || dtype.is_enum() {
+        return from_categories(model, &dtype);
+    }
+    if !model.categories.is_empty() {
+        return Err(invalid(format!(
+            "{} has categories but data type {}",
+            model.name, model.dtype
+        )));
+    }
+    from_text(&model.name, &dtype, &model.values)
+}
+
+#[cfg(feature = "categorical")]
+fn categories(column: &Series) -> LadResult<Vec<String>> {
+    if !column.dtype().is_categorical() && !column.dtype().is_enum() {
+        return Ok(Vec::new());
+    }
+    let categories = column.categorical()?.get_rev_map().get_categories();
+    Ok(categories.values_iter().map(ToString::to_string).collect())
+}
+
+#[cfg(not(feature = "categorical"))]
+fn categories(_column: &Series) -> LadResult<Vec<String>> {
+    Ok(Vec::new())
+}
+
+/// Categorical or enum column whose categories, and so physical values, are the saved ones.
+#[cfg(feature = "categorical")]
+fn from_categories(model: &ValuesModel, dtype: &DataType) -> LadResult<Series> {
+    let mut index = HashMap::new();
+    for (i, category) in model.categories.iter().enumerate() {
+        if index.insert(category.as_str(), i as IdxSize).is_some() {
+            return Err(invalid(format!(
+                "{} has category {category} twice",
+                model.name
+            )));
+        }
+    }
+    let dtype = match dtype {
+        DataType::Enum(_, _) => {
+            create_enum_dtype(Utf8ViewArray::from_slice_values(&model.categories))
+        }
+        dtype => dtype.clone(),
+    };
+    let categories = Series::new(model.name.as_str().into(), &model.categories).cast(&dtype)?;
+    let indices = model
+        .values
+        .iter()
+        .map(|x| {
+            index.get(x.as_str()).copied().ok_or_else(|| {
+                invalid(format!(
+                    "{} has value {x} outside its categories",
+                    model.name
+                ))
+            })
+        })
+        .collect::<LadResult<Vec<_>>>()?;
+    Ok(categories.take(&IdxCa::from_vec(model.name.as_str().into(), indices))?)
+}
+
+#[cfg(not(feature = "categorical"))]
+fn from_categories(_model: &ValuesModel, _dtype: &DataType) -> LadResult<Series> {
+    unreachable!("categorical data types are only parsed with the categorical feature")
+}
+
+/// Text of every value of `column` from which [`from_text`] restores it. Temporal values are
+/// written as their physical value and floats in full, other values as polars shows them.
+fn to_text(column: &Series) -> LadResult<Vec<String>> {
+    let values = if column.dtype().is_temporal() {
+        column.to_physical_repr().into_owned()
+    } else if column.dtype().is_float() {
+        column.cast(&DataType::Float64)?
+    } else {
+        column.clone()
+    };
+    Ok(values
+        .iter()
+        .map(|x| match x {
+            AnyValue::Float64(x) => x.to_string(),
+            x => label_text(&x),
+        })
+        .collect())
+}
+
+/// Series `name` of data type `dtype` holding `values`, as [`to_text`] writes them.
+fn from_text(name: &str, dtype: &DataType, values: &[String]) -> LadResult<Series> {
+    let text = Series::new(name.into(), values);
+    Ok(match dtype {
+        DataType::Boolean => {
+            let values = values
+                .iter()
+                .map(|x| match x.as_str() {
+                    "true" => Ok(true),
+                    "false" => Ok(false),
+                    _ => Err(invalid(format!("{x} is not a boolean"))),
+                })
+                .collect::<LadResult<Vec<_>>>()?;
+            Series::new(name.into(), values)
+        }
+        dtype if dtype.is_temporal() => text.strict_cast(&dtype.to_physical())?.cast(dtype)?,
+        dtype => text.strict_cast(dtype)?,
+    })
+}
+
+impl Binarizer {
+    pub fn to_json(&self) -> LadResult<String> {
+        to_json(self.to_model()?)
+    }
+
+    pub fn from_json(json: &str) -> LadResult<Self> {
+        Self::from_model(from_json(json)?)
+    }
+
+    pub fn to_bytes(&self) -> LadResult<Vec<u8>> {
+        to_bytes(self.to_model()?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> LadResult<Self> {
+        Self::from_model(from_bytes(bytes)?)
+    }
+}
+
+impl RuleGenerator {
+    pub fn to_json(&self) -> LadResult<String> {
+        to_json(self.to_model()?)
+    }
+
+    pub fn from_json(json: &str) -> LadResult<Self> {
+        Self::from_model(from_json(json)?)
+    }
+
+    pub fn to_bytes(&self) -> LadResult<Vec<u8>> {
+        to_bytes(self.to_model()?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> LadResult<Self> {
+        Self::from_model(from_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> DataFrame {
+        let data = df!(
+            "x" => [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+            "y" => [Some(3), None, Some(4), Some(1), Some(5), None, Some(2), Some(6), Some(5), Some(3)],
+            "color" => ["red", "blue", "red", "green", "blue", "red", "green", "blue", "red", "green"],
+        )
+        .unwrap();
+        #[cfg(feature = "categorical")]
+        let data = {
+            let mut data = data;
+            let size = Series::new(
+                "size".into(),
+                ["s", "m", "l", "m", "s", "l", "l", "m", "s", "s"],
+            );
+            let dtype = DataType::Categorical(None, CategoricalOrdering::Physical);
+            data.with_column(size.cast(&dtype).unwrap()).unwrap();
+            data
+        };
+        data
+    }
+
+    /// The same classes as labels of every data type models save.
+    fn labels() -> Vec<Series> {
+        let classes: [usize; 10] = [0, 0, 1, 0, 1, 1, 2, 2, 1, 2];
+        let ints = Series::new("label".into(), classes.map(|x| x as i64));
+        let text = Series::new("label".into(), classes.map(|x| ["b", "a", "c"][x]));
+        let cast = |series: &Series, dtype: DataType| series.cast(&dtype).unwrap();
+        let labels = vec![
+            ints.clone(),
+            cast(&ints, DataType::UInt32),
+            cast(&ints, DataType::Float64) / 3.0,
+            text.clone(),
+            Series::new("label".into(), classes.map(|x| x == 1)),
+            // Nullable, without nulls as labels may not have any
+            Series::new("label".into(), classes.map(|x| Some(x as i32))),
+            cast(&ints, DataType::Date),
+            cast(
+                &(&ints * 3_600_000_i64),
+                DataType::Datetime(TimeUnit::Milliseconds, None),
+            ),
+            cast(
+                &(&ints * 3_600_000_000_i64),
+                DataType::Datetime(TimeUnit::Microseconds, Some("Europe/Amsterdam".into())),
+            ),
+            cast(&ints, DataType::Duration(TimeUnit::Nanoseconds)),
+        ];
+        #[cfg(feature = "categorical")]
+        let labels = [
+            labels,
+            vec![
+                cast(
+                    &text,
+                    DataType::Categorical(None, CategoricalOrdering::Physical),
+                ),
+                cast(
+                    &text,
+                    DataType::Categorical(None, CategoricalOrdering::Lexical),
+                ),
+                cast(
+                    &text,
+                    create_enum_dtype(Utf8ViewArray::from_slice_values(["d", "c", "b", "a"])),
+                ),
+            ],
+        ]
+        .concat();
+        labels
+    }
+
+    fn fit(labels: &Series) -> (Binarizer, RuleGenerator) {
+        let data = data();
+        let mut binarizer = Binarizer::new(0.0, 3, usize::MAX);
+        binarizer.generate_cutpoints(&data, labels, None).unwrap();
+        let mut generator = RuleGenerator::new(&binarizer, 0);
+        generator
+            .fit(&binarizer.transform(&data).unwrap(), labels, None)
+            .unwrap();
+        (binarizer, generator)
+    }
+
+    fn assert_same_values(restored: &Series, expected: &Series) {
+        assert_eq!(restored.name(), expected.name());
+        assert_eq!(dtype_name(restored.dtype()), dtype_name(expected.dtype()));
+        assert_eq!(restored.dtype(), expected.dtype());
+        assert_eq!(categories(restored).unwrap(), categories(expected).unwrap());
+        assert_eq!(to_text(restored).unwrap(), to_text(expected).unwrap());
+    }
+
+    #[test]
+    fn fitted_models_round_trip() {
+        let data = data();
+        for labels in labels() {
+            let (binarizer, generator) = fit(&labels);
+            let predictions = generator.predict(&data).unwrap();
+            assert_same_values(&predictions, &labels);
+            let binary = binarizer.transform(&data).unwrap();
+            for restored in [
+                RuleGenerator::from_json(&generator.to_json().unwrap()).unwrap(),
+                RuleGenerator::from_bytes(&generator.to_bytes().unwrap()).unwrap(),
+            ] {
+                assert_same_values(&restored.predict(&data).unwrap(), &predictions);
+                assert_same_values(&restored.get_classes(), &generator.get_classes());
+                assert_eq!(restored.get_rules(), generator.get_rules());
+            }
+            for restored in [
+                Binarizer::from_json(&binarizer.to_json().unwrap()).unwrap(),
+                Binarizer::from_bytes(&binarizer.to_bytes().unwrap()).unwrap(),
+            ] {
+                assert!(restored.transform(&data).unwrap().equals_missing(&binary));
+            }
+        }
+    }
+
+    #[test]
+    fn unsaveable_levels_fail_when_saving() {
+        let data = df!(
+            "x" => [1.0, 2.0, 3.0, 4.0],
+            "blob" => [b"a".as_ref(), b"b", b"a", b"b"],
+        )
+        .unwrap();
+        let labels = Series::new("label".into(), [0, 1, 0, 1]);
+        let mut binarizer = Binarizer::new(0.0, 2, usize::MAX);
+        binarizer.generate_cutpoints(&data, &labels, None).unwrap();
+        let binary = binarizer.transform(&data).unwrap();
+        let names = binary.get_column_names();
+        assert_eq!(names.iter().filter(|x| x.starts_with("blob = ")).count(), 2);
+        assert!(matches!(
+            binarizer.to_json(),
+            Err(LadError::UnsupportedDtype { column, .. }) if column == "blob"
+        ));
+        assert!(binarizer.to_bytes().is_err());
+    }
+
+    #[test]
+    fn labels_with_missing_values_are_rejected() {
+        let data = data();
+        let labels = Series::new(
+            "label".into(),
+            (0..10)
+                .map(|x| (x != 4).then_some(x % 2))
+                .collect::<Vec<_>>(),
+        );
+        let mut binarizer = Binarizer::new(0.0, 3, usize::MAX);
+        binarizer.generate_cutpoints(&data, &labels, None).unwrap();
+        let mut generator = RuleGenerator::new(&binarizer, 0);
+        let binary = binarizer.transform(&data).unwrap();
+        assert!(matches!(
+            generator.fit(&binary, &labels, None),
+            Err(LadError::MissingLabels(1))
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use polars::prelude::AnyValue;
+
+use crate::Pattern;
+
+/// Something that happened while fitting a model.
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// A candidate cutpoint between two label changes was scored.
+    CutpointScored {
+        feature: &'a str,
+        cutpoint: &'a AnyValue<'a>,
+        score: f64,
+    },
+    DegreeStarted {
+        degree: usize,
+    },
+    /// `candidates` is the number of patterns carried over to the next degree.
+    DegreeFinished {
+        degree: usize,
+        elapsed: Duration,
+        candidates: usize,
+    },
+    PatternAccepted {
+        pattern: &'a Pattern,
+    },
+    /// Number of observations per label not yet covered by any pattern.
+    Remaining {
+        labels: &'a [String],
+        counts: &'a [usize],
+    },
+}
+
+/// Receives the events emitted by [`Binarizer`](crate::Binarizer) and
+/// [`RuleGenerator`](crate::RuleGenerator).
+pub trait Observer: Send + Sync {
+    fn on_event(&self, event: &Event<'_>);
+}
+
+/// Lets another thread stop [`RuleGenerator::fit`](crate::RuleGenerator::fit), which keeps the
+/// rules found so far. Clones share the same state, and a cancelled token stays cancelled.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Prints every event to stdout.
+pub struct StdoutObserver;
+
+impl Observer for StdoutObserver {
+    fn on_event(&self, event: &Event<'_>) {
+        match event {
+            Event::CutpointScored {
+                feature,
+                cutpoint,
+                score,
+            } => println!("{feature} > {cutpoint}: {score}"),
+            Event::DegreeStarted { degree } => println!("{degree}"),
+            Event::DegreeFinished {
+                degree,
+                elapsed,
+                candidates,
+            } => println!(
+                "Degree {degree} took {} milliseconds, {candidates} candidates left",
+                elapsed.as_millis()
+            ),
+            Event::PatternAccepted { pattern } => {
+                println!("{}: {:?}", pattern.label, pattern.literals);
+            }
+            Event::Remaining { labels, counts } => println!("{labels:?} {counts:?}"),
+        }
+    }
+}
@@ -0,0 +1,553 @@
+// pyo3 0.22 `#[pymethods]` trip this lint on every `PyResult` returning method
+#![allow(clippy::useless_conversion)]
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use polars::prelude::{IntoSeries, Series};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList};
+use pyo3_polars::{PyDataFrame, PySeries};
+
+use crate::binarization::rule_generation::label_text;
+use crate::{
+    Binarizer, CancellationToken, CutpointCriterion, DecisionMode, FallbackPolicy, FitReport,
+    MinCoverage, NullPolicy, Pattern, PatternStrategy, PatternType, RuleGenerator, SelectionMode,
+    StopReason, SupportSetSelector,
+};
+
+create_exception!(
+    lad_rs,
+    LadError,
+    PyValueError,
+    "Error raised by lad_rs models."
+);
+
+impl From<crate::LadError> for PyErr {
+    fn from(e: crate::LadError) -> Self {
+        LadError::new_err(e.to_string())
+    }
+}
+
+/// Accepts polars DataFrames as is and converts anything else (pandas, pyarrow, dicts) through
+/// `polars.DataFrame`, which goes through Arrow.
+fn to_dataframe(data: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+    if let Ok(df) = data.extract::<PyDataFrame>() {
+        return Ok(df);
+    }
+    let polars = data.py().import_bound("polars")?;
+    polars.call_method1("DataFrame", (data,))?.extract()
+}
+
+fn to_null_policy(policy: &str) -> PyResult<NullPolicy> {
+    match policy {
+        "indicator" => Ok(NullPolicy::Indicator),
+        "unknown" => Ok(NullPolicy::Unknown),
+        "drop" => Ok(NullPolicy::Drop),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown null policy {policy}, expected indicator, unknown or drop"
+        ))),
+    }
+}
+
+fn to_criterion(criterion: &str) -> PyResult<CutpointCriterion> {
+    match criterion {
+        "score" => Ok(CutpointCriterion::Score),
+        "information_gain" => Ok(CutpointCriterion::InformationGain),
+        "gain_ratio" => Ok(CutpointCriterion::GainRatio),
+        "gini" => Ok(CutpointCriterion::Gini),
+        "chi_square" => Ok(CutpointCriterion::ChiSquare),
+        "mdl" => Ok(CutpointCriterion::Mdl),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown criterion {criterion}, expected score, information_gain, gain_ratio, gini, \
+             chi_square or mdl"
+        ))),
+    }
+}
+
+fn to_selection_mode(mode: &str) -> PyResult<SelectionMode> {
+    match mode {
+        "greedy" => Ok(SelectionMode::Greedy),
+        "exact" => Ok(SelectionMode::Exact),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown mode {mode}, expected greedy or exact"
+        ))),
+    }
+}
+
+fn to_decision_mode(mode: &str) -> PyResult<DecisionMode> {
+    match mode {
+        "first_match" => Ok(DecisionMode::FirstMatch),
+        "majority_vote" => Ok(DecisionMode::MajorityVote),
+        "weighted_discriminant" => Ok(DecisionMode::WeightedDiscriminant),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown decision mode {mode}, expected first_match, majority_vote or weighted_discriminant"
+        ))),
+    }
+}
+
+fn to_strategy(strategy: &str) -> PyResult<PatternStrategy> {
+    match strategy {
+        "bottom_up" => Ok(PatternStrategy::BottomUp),
+        "top_down" => Ok(PatternStrategy::TopDown),
+        "max_coverage" => Ok(PatternStrategy::MaxCoverage),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown strategy {strategy}, expected bottom_up, top_down or max_coverage"
+        ))),
+    }
+}
+
+fn to_pattern_type(pattern_type: &str) -> PyResult<PatternType> {
+    match pattern_type {
+        "prime" => Ok(PatternType::Prime),
+        "spanned" => Ok(PatternType::Spanned),
+        "strong" => Ok(PatternType::Strong),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown pattern type {pattern_type}, expected prime, spanned or strong"
+        ))),
+    }
+}
+
+fn to_min_coverage(value: &Bound<'_, PyAny>) -> PyResult<MinCoverage> {
+    if let Ok(count) = value.extract::<usize>() {
+        return Ok(MinCoverage::Count(count));
+    }
+    Ok(MinCoverage::Fraction(value.extract()?))
+}
+
+/// Text of a label, as [`RuleGenerator::get_labels`] gives it.
+fn to_label(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    let series = to_series(PyList::new_bound(value.py(), [value]).as_any())?;
+    let label = series.0.get(0).map_err(crate::LadError::from)?;
+    Ok(label_text(&label))
+}
+
+/// Labels as a series. The extension is built without categoricals, see `Cargo.toml`, so
+/// categorical and enum labels cross as strings, their data type being returned alongside.
+fn to_labels(labels: &Bound<'_, PyAny>) -> PyResult<(PySeries, Option<PyObject>)> {
+    let polars = labels.py().import_bound("polars")?;
+    let series = if labels.is_instance(&polars.getattr("Series")?)? {
+        labels.clone()
+    } else {
+        polars.call_method1("Series", (labels,))?
+    };
+    let dtype = series.getattr("dtype")?;
+    if dtype.eq(polars.getattr("Categorical")?)? || dtype.eq(polars.getattr("Enum")?)? {
+        let strings = series.call_method1("cast", (polars.getattr("String")?,))?;
+        return Ok((strings.extract()?, Some(dtype.unbind())));
+    }
+    Ok((series.extract()?, None))
+}
+
+fn to_series(data: &Bound<'_, PyAny>) -> PyResult<PySeries> {
+    if let Ok(series) = data.extract::<PySeries>() {
+        return Ok(series);
+    }
+    let polars = data.py().import_bound("polars")?;
+    polars.call_method1("Series", (data,))?.extract()
+}
+
+#[pyclass(name = "Binarizer", module = "lad_rs")]
+#[derive(Clone)]
+pub struct PyBinarizer(Binarizer);
+
+#[pymethods]
+impl PyBinarizer {
+    #[new]
+    #[pyo3(signature = (threshold=0.0, nominal_size=2, max_cutpoints_per_column=usize::MAX, intervals=false, null_policy="indicator", criterion="score"))]
+    fn new(
+        threshold: f64,
+        nominal_size: usize,
+        max_cutpoints_per_column: usize,
+        intervals: bool,
+        null_policy: &str,
+        criterion: &str,
+    ) -> PyResult<Self> {
+        Ok(Self(
+            Binarizer::new(threshold, nominal_size, max_cutpoints_per_column)
+                .with_intervals(intervals)
+                .with_null_policy(to_null_policy(null_policy)?)
+                .with_criterion(to_criterion(criterion)?),
+        ))
+    }
+
+    /// `weights`, one per row, weigh the rows when scoring cutpoints.
+    #[pyo3(signature = (data, label, weights=None))]
+    fn fit(
+        &mut self,
+        data: &Bound<'_, PyAny>,
+        label: &Bound<'_, PyAny>,
+        weights: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let data = to_dataframe(data)?;
+        let (label, _) = to_labels(label)?;
+        let weights = weights.map(to_series).transpose()?;
+        Ok(self
+            .0
+            .generate_cutpoints(&data.0, &label.0, weights.as_ref().map(|x| &x.0))?)
+    }
+
+    fn transform(&self, data: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        Ok(PyDataFrame(self.0.transform(&to_dataframe(data)?.0)?))
+    }
+
+    fn kept_rows(&self, data: &Bound<'_, PyAny>) -> PyResult<PySeries> {
+        let mask = self.0.kept_rows(&to_dataframe(data)?.0)?;
+        Ok(PySeries(mask.into_series()))
+    }
+
+    fn get_cutpoints(&self) -> Vec<PySeries> {
+        self.0.get_cutpoints().into_iter().map(PySeries).collect()
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        Ok(self.0.to_json()?)
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(Self(Binarizer::from_json(json)?))
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.0.to_bytes()?))
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        Ok(Self(Binarizer::from_bytes(bytes)?))
+    }
+}
+
+#[pyclass(name = "SupportSetSelector", module = "lad_rs")]
+pub struct PySupportSetSelector(SupportSetSelector);
+
+#[pymethods]
+impl PySupportSetSelector {
+    #[new]
+    #[pyo3(signature = (mode="greedy"))]
+    fn new(mode: &str) -> PyResult<Self> {
+        Ok(Self(SupportSetSelector::new(to_selection_mode(mode)?)))
+    }
+
+    /// Prunes the cutpoints of a fitted binarizer in place.
+    fn select(
+        &self,
+        binarizer: &mut PyBinarizer,
+        data: &Bound<'_, PyAny>,
+        label: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        let data = to_dataframe(data)?;
+        let (label, _) = to_labels(label)?;
+        Ok(self.0.select(&mut binarizer.0, &data.0, &label.0)?)
+    }
+}
+
+/// Rule with its statistics on the training data, see the Rust `Pattern`.
+#[pyclass(name = "Pattern", module = "lad_rs", get_all)]
+pub struct PyPattern {
+    label: String,
+    literals: BTreeSet<(bool, String)>,
+    degree: usize,
+    coverage: Vec<f64>,
+    prevalence: f64,
+    homogeneity: f64,
+    index: usize,
+}
+
+impl From<Pattern> for PyPattern {
+    fn from(pattern: Pattern) -> Self {
+        Self {
+            label: pattern.label,
+            literals: pattern.literals,
+            degree: pattern.degree,
+            coverage: pattern.coverage,
+            prevalence: pattern.prevalence,
+            homogeneity: pattern.homogeneity,
+            index: pattern.index,
+        }
+    }
+}
+
+#[pymethods]
+impl PyPattern {
+    fn __repr__(&self) -> String {
+        format!(
+            "Pattern(label={}, literals={:?}, prevalence={}, homogeneity={})",
+            self.label, self.literals, self.prevalence, self.homogeneity
+        )
+    }
+}
+
+/// Stops a running `RuleGenerator.fit` from another thread once cancelled.
+#[pyclass(name = "CancellationToken", module = "lad_rs")]
+#[derive(Clone, Default)]
+pub struct PyCancellationToken(CancellationToken);
+
+#[pymethods]
+impl PyCancellationToken {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// Why `RuleGenerator.fit` stopped, see the Rust `FitReport`. `elapsed` is in seconds.
+#[pyclass(name = "FitReport", module = "lad_rs", get_all)]
+pub struct PyFitReport {
+    stop_reason: &'static str,
+    elapsed: f64,
+    uncovered_rows: usize,
+}
+
+impl From<FitReport> for PyFitReport {
+    fn from(report: FitReport) -> Self {
+        Self {
+            stop_reason: match report.stop_reason {
+                StopReason::Completed => "completed",
+                StopReason::MaxDuration => "max_duration",
+                StopReason::MaxCandidates => "max_candidates",
+                StopReason::Cancelled => "cancelled",
+            },
+            elapsed: report.elapsed.as_secs_f64(),
+            uncovered_rows: report.uncovered_rows,
+        }
+    }
+}
+
+#[pymethods]
+impl PyFitReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "FitReport(stop_reason={}, elapsed={}, uncovered_rows={})",
+            self.stop_reason, self.elapsed, self.uncovered_rows
+        )
+    }
+}
+
+/// Predicted label, whether it is the fallback label, and the `(label, literals)` of the rules
+/// covering the row.
+type PyExplanation = (String, bool, Vec<(String, Vec<String>)>);
+
+/// Rule generator and the data type of categorical or enum training labels, which predictions
+/// are cast back to. Saved models forget it and predict strings for such labels.
+#[pyclass(name = "RuleGenerator", module = "lad_rs")]
+pub struct PyRuleGenerator(RuleGenerator, Option<PyObject>);
+
+impl PyRuleGenerator {
+    /// `labels` in the data type of the training labels.
+    fn restore(&self, py: Python<'_>, labels: Series) -> PyResult<PyObject> {
+        let labels = PySeries(labels).into_py(py);
+        match &self.1 {
+            Some(dtype) => labels.call_method1(py, "cast", (dtype,)),
+            None => Ok(labels),
+        }
+    }
+}
+
+#[pymethods]
+impl PyRuleGenerator {
+    #[new]
+    /// `min_coverage` is a number of rows when given an int and a share of the label when given
+    /// a float, `label_min_coverage` maps labels to their own minimum coverage and
+    /// `max_patterns` labels to the most patterns they may get. `fallback` is `most_remaining`
+    /// or `priors`, unless `fallback_label` fixes the label. `max_duration` is in seconds.
+    #[pyo3(signature = (
+        binarizer,
+        max=0,
+        decision_mode="first_match",
+        min_purity=1.0,
+        min_coverage=None,
+        strategy="bottom_up",
+        pattern_type="prime",
+        fallback="most_remaining",
+        fallback_label=None,
+        label_min_coverage=None,
+        max_patterns=None,
+        max_duration=None,
+        max_candidates_per_degree=None,
+        cancellation_token=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        binarizer: &PyBinarizer,
+        max: usize,
+        decision_mode: &str,
+        min_purity: f64,
+        min_coverage: Option<&Bound<'_, PyAny>>,
+        strategy: &str,
+        pattern_type: &str,
+        fallback: &str,
+        fallback_label: Option<&Bound<'_, PyAny>>,
+        label_min_coverage: Option<&Bound<'_, PyDict>>,
+        max_patterns: Option<&Bound<'_, PyDict>>,
+        max_duration: Option<f64>,
+        max_candidates_per_degree: Option<usize>,
+        cancellation_token: Option<PyCancellationToken>,
+    ) -> PyResult<Self> {
+        let mut generator = RuleGenerator::new(&binarizer.0, max)
+            .with_decision_mode(to_decision_mode(decision_mode)?)
+            .with_min_purity(min_purity)
+            .with_strategy(to_strategy(strategy)?)
+            .with_pattern_type(to_pattern_type(pattern_type)?);
+        if let Some(min_coverage) = min_coverage {
+            generator = generator.with_min_coverage(to_min_coverage(min_coverage)?);
+        }
+        let fallback = match (fallback, fallback_label) {
+            (_, Some(label)) => FallbackPolicy::Fixed(to_label(label)?),
+            ("most_remaining", None) => FallbackPolicy::MostRemaining,
+            ("priors", None) => FallbackPolicy::Priors,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown fallback {fallback}, expected most_remaining or priors"
+                )))
+            }
+        };
+        generator = generator.with_fallback(fallback);
+        for (label, min_coverage) in label_min_coverage.iter().flat_map(|x| x.iter()) {
+            generator = generator
+                .with_label_min_coverage(to_label(&label)?, to_min_coverage(&min_coverage)?);
+        }
+        for (label, count) in max_patterns.iter().flat_map(|x| x.iter()) {
+            generator = generator.with_max_patterns(to_label(&label)?, count.extract()?);
+        }
+        if let Some(seconds) = max_duration {
+            let duration = Duration::try_from_secs_f64(seconds)
+                .map_err(|e| PyValueError::new_err(format!("max_duration {seconds}: {e}")))?;
+            generator = generator.with_max_duration(duration);
+        }
+        if let Some(max_candidates) = max_candidates_per_degree {
+            generator = generator.with_max_candidates_per_degree(max_candidates);
+        }
+        if let Some(token) = cancellation_token {
+            generator = generator.with_cancellation_token(token.0);
+        }
+        Ok(Self(generator, None))
+    }
+
+    /// Fits on data already transformed by the binarizer, rows weighing their `weights` if
+    /// given. The GIL is released while fitting so that other threads can cancel it.
+    #[pyo3(signature = (data, labels, weights=None))]
+    fn fit(
+        &mut self,
+        py: Python<'_>,
+        data: &Bound<'_, PyAny>,
+        labels: &Bound<'_, PyAny>,
+        weights: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyFitReport> {
+        let data = to_dataframe(data)?;
+        let (labels, dtype) = to_labels(labels)?;
+        let weights = weights.map(to_series).transpose()?;
+        self.1 = dtype;
+        let report = py.allow_threads(|| {
+            self.0
+                .fit(&data.0, &labels.0, weights.as_ref().map(|x| &x.0))
+        })?;
+        Ok(report.into())
+    }
+
+    /// Predicts on raw data, which is binarized first, as a series like the training labels.
+    fn predict(&self, py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.restore(py, self.0.predict(&to_dataframe(data)?.0)?)
+    }
+
+    /// Extends every rule to its spanned pattern, `data` being binarized as for `fit`.
+    fn span(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        Ok(self.0.span(&to_dataframe(data)?.0)?)
+    }
+
+    /// Drops redundant rules, `data` being binarized as for `fit`. Returns the dropped rules.
+    #[pyo3(signature = (data, labels, mode="greedy"))]
+    fn prune(
+        &mut self,
+        data: &Bound<'_, PyAny>,
+        labels: &Bound<'_, PyAny>,
+        mode: &str,
+    ) -> PyResult<Vec<PyPattern>> {
+        let data = to_dataframe(data)?;
+        let (labels, _) = to_labels(labels)?;
+        let report = self.0.prune(&data.0, &labels.0, to_selection_mode(mode)?)?;
+        Ok(report.dropped.into_iter().map(PyPattern::from).collect())
+    }
+
+    /// Per label scores, one column per label.
+    fn decision_function(&self, data: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        Ok(PyDataFrame(
+            self.0.decision_function(&to_dataframe(data)?.0)?,
+        ))
+    }
+
+    /// Per label probabilities, one column per label.
+    fn predict_proba(&self, data: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        Ok(PyDataFrame(self.0.predict_proba(&to_dataframe(data)?.0)?))
+    }
+
+    /// Explains the prediction of every row.
+    fn explain(&self, data: &Bound<'_, PyAny>) -> PyResult<Vec<PyExplanation>> {
+        Ok(self
+            .0
+            .explain(&to_dataframe(data)?.0)?
+            .into_iter()
+            .map(|x| {
+                let rules = x.rules.into_iter().map(|r| (r.label, r.literals)).collect();
+                (x.label, x.used_fallback, rules)
+            })
+            .collect())
+    }
+
+    fn get_rules(&self) -> Vec<PyPattern> {
+        self.0
+            .get_rules()
+            .into_iter()
+            .map(PyPattern::from)
+            .collect()
+    }
+
+    fn get_labels(&self) -> Vec<String> {
+        self.0.get_labels()
+    }
+
+    fn get_classes(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.restore(py, self.0.get_classes())
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        Ok(self.0.to_json()?)
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(Self(RuleGenerator::from_json(json)?, None))
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.0.to_bytes()?))
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        Ok(Self(RuleGenerator::from_bytes(bytes)?, None))
+    }
+}
+
+#[pymodule]
+pub fn _lad_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBinarizer>()?;
+    m.add_class::<PyCancellationToken>()?;
+    m.add_class::<PyFitReport>()?;
+    m.add_class::<PyPattern>()?;
+    m.add_class::<PyRuleGenerator>()?;
+    m.add_class::<PySupportSetSelector>()?;
+    m.add("LadError", m.py().get_type_bound::<LadError>())?;
+    Ok(())
+}